@@ -0,0 +1,103 @@
+//! A tiny command line parser, enough for `--flag` and `--key value` options.
+//!
+use std::str::FromStr;
+
+#[derive(Debug)]
+pub struct Args {
+    rest: Vec<String>,
+}
+
+impl Args {
+    pub fn new<I: IntoIterator<Item = String>>(args: I) -> Args {
+        Args {
+            rest: args.into_iter().collect(),
+        }
+    }
+
+    /// Arguments of the running process, without the program name.
+    pub fn from_env() -> Args {
+        Self::new(std::env::args().skip(1))
+    }
+
+    /// Take the first argument if it is not an option.
+    pub fn subcommand(&mut self) -> Option<String> {
+        match self.rest.first() {
+            Some(s) if !s.starts_with('-') => Some(self.rest.remove(0)),
+            _ => None,
+        }
+    }
+
+    pub fn flag(&mut self, name: &str) -> bool {
+        match self.rest.iter().position(|a| a == name) {
+            Some(idx) => {
+                self.rest.remove(idx);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Take `name value` or `name=value`.
+    pub fn value(&mut self, name: &str) -> Result<Option<String>, String> {
+        let prefix = format!("{}=", name);
+        for idx in 0..self.rest.len() {
+            if let Some(v) = self.rest[idx].strip_prefix(&prefix) {
+                let v = v.to_string();
+                self.rest.remove(idx);
+                return Ok(Some(v));
+            }
+            if self.rest[idx] == name {
+                if idx + 1 == self.rest.len() {
+                    return Err(format!("{} expects a value", name));
+                }
+                self.rest.remove(idx);
+                return Ok(Some(self.rest.remove(idx)));
+            }
+        }
+        Ok(None)
+    }
+
+    pub fn parsed<T: FromStr>(&mut self, name: &str) -> Result<Option<T>, String>
+    where
+        T::Err: std::fmt::Display,
+    {
+        match self.value(name)? {
+            Some(v) => v
+                .parse()
+                .map(Some)
+                .map_err(|e| format!("bad value {:?} for {}: {}", v, name, e)),
+            None => Ok(None),
+        }
+    }
+
+    /// Complain about anything nobody asked for.
+    pub fn finish(self) -> Result<(), String> {
+        match self.rest.first() {
+            Some(a) => Err(format!("unexpected argument {:?}", a)),
+            None => Ok(()),
+        }
+    }
+}
+
+#[test]
+fn test_args() {
+    let mut args = Args::new(
+        ["run", "--day", "10", "--part=2", "--all"]
+            .into_iter()
+            .map(String::from),
+    );
+    assert_eq!(args.subcommand().as_deref(), Some("run"));
+    assert_eq!(args.parsed::<u8>("--day"), Ok(Some(10)));
+    assert_eq!(args.value("--part"), Ok(Some("2".to_string())));
+    assert_eq!(args.value("--input"), Ok(None));
+    assert!(args.flag("--all"));
+    assert!(args.finish().is_ok());
+}
+
+#[test]
+fn test_args_errors() {
+    let mut args = Args::new(["--day", "x", "--input"].into_iter().map(String::from));
+    assert!(args.parsed::<u8>("--day").is_err());
+    assert!(args.value("--input").is_err());
+    assert!(Args::new(["stray".to_string()]).finish().is_err());
+}
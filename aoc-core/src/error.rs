@@ -0,0 +1,167 @@
+use std::fmt;
+
+use crate::Part;
+
+/// Everything that can go wrong between reading an input and printing its
+/// answers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The input could not be read.
+    Input(Diagnostic),
+    /// The input is malformed.
+    Parse(Diagnostic),
+    /// The input is well formed, but the solver found no answer for it.
+    Solve(Diagnostic),
+}
+
+/// Where and why an [`Error`] happened. Lines and columns count from 1.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Diagnostic {
+    pub day: Option<u8>,
+    pub part: Option<Part>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    /// The offending piece of input.
+    pub text: Option<String>,
+    pub reason: String,
+}
+
+impl Error {
+    pub fn input(reason: impl Into<String>) -> Error {
+        Error::Input(Self::diagnostic_of(reason))
+    }
+
+    pub fn parse(reason: impl Into<String>) -> Error {
+        Error::Parse(Self::diagnostic_of(reason))
+    }
+
+    pub fn solve(reason: impl Into<String>) -> Error {
+        Error::Solve(Self::diagnostic_of(reason))
+    }
+
+    fn diagnostic_of(reason: impl Into<String>) -> Diagnostic {
+        Diagnostic {
+            reason: reason.into(),
+            ..Diagnostic::default()
+        }
+    }
+
+    pub fn diagnostic(&self) -> &Diagnostic {
+        match self {
+            Error::Input(d) | Error::Parse(d) | Error::Solve(d) => d,
+        }
+    }
+
+    fn diagnostic_mut(&mut self) -> &mut Diagnostic {
+        match self {
+            Error::Input(d) | Error::Parse(d) | Error::Solve(d) => d,
+        }
+    }
+
+    pub fn at(self, line: usize, column: usize) -> Error {
+        self.at_line(line).at_column(column)
+    }
+
+    pub fn at_line(mut self, line: usize) -> Error {
+        self.diagnostic_mut().line = Some(line);
+        self
+    }
+
+    pub fn at_column(mut self, column: usize) -> Error {
+        self.diagnostic_mut().column = Some(column);
+        self
+    }
+
+    /// Move the position down by `lines`, for parsers that were handed only
+    /// a tail of the input.
+    pub fn offset_lines(mut self, lines: usize) -> Error {
+        if let Some(line) = self.diagnostic_mut().line.as_mut() {
+            *line += lines;
+        }
+        self
+    }
+
+    /// Move the position right by `columns`, for parsers that were handed
+    /// only a piece of a line.
+    pub fn offset_columns(mut self, columns: usize) -> Error {
+        if let Some(column) = self.diagnostic_mut().column.as_mut() {
+            *column += columns;
+        }
+        self
+    }
+
+    pub fn with_text(mut self, text: impl Into<String>) -> Error {
+        self.diagnostic_mut().text = Some(text.into());
+        self
+    }
+
+    /// Tag the error with the day it came from, unless it already has one.
+    pub fn in_day(mut self, day: u8) -> Error {
+        self.diagnostic_mut().day.get_or_insert(day);
+        self
+    }
+
+    pub fn in_part(mut self, part: Part) -> Error {
+        self.diagnostic_mut().part.get_or_insert(part);
+        self
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let d = self.diagnostic();
+        match (d.day, d.part) {
+            (Some(day), Some(part)) => write!(f, "day {:02} part {}: ", day, part)?,
+            (Some(day), None) => write!(f, "day {:02}: ", day)?,
+            (None, Some(part)) => write!(f, "part {}: ", part)?,
+            (None, None) => (),
+        }
+        match (d.line, d.column) {
+            (Some(line), Some(column)) => write!(f, "{}:{}: ", line, column)?,
+            (Some(line), None) => write!(f, "line {}: ", line)?,
+            (None, Some(column)) => write!(f, "column {}: ", column)?,
+            (None, None) => (),
+        }
+        match self {
+            Error::Input(_) => write!(f, "input error: ")?,
+            Error::Parse(_) => write!(f, "parse error: ")?,
+            Error::Solve(_) => write!(f, "solve error: ")?,
+        }
+        write!(f, "{}", d.reason)?;
+        if let Some(text) = &d.text {
+            write!(f, " at {:?}", text)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[test]
+fn test_display() {
+    let e = Error::parse("invalid card")
+        .at(3, 2)
+        .with_text("X")
+        .in_day(7)
+        .in_day(8);
+    assert_eq!(
+        e.to_string(),
+        "day 07: 3:2: parse error: invalid card at \"X\""
+    );
+    assert_eq!(
+        Error::solve("no goal")
+            .in_part(Part::Two)
+            .in_day(8)
+            .to_string(),
+        "day 08 part 2: solve error: no goal"
+    );
+}
+
+#[test]
+fn test_offset_lines() {
+    let e = Error::parse("bad").at_line(1).offset_lines(2);
+    assert_eq!(e.diagnostic().line, Some(3));
+    assert_eq!(Error::parse("bad").offset_lines(2).diagnostic().line, None);
+    let e = Error::parse("bad").at_column(2).offset_columns(4);
+    assert_eq!(e.diagnostic().column, Some(6));
+}
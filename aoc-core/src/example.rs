@@ -0,0 +1,103 @@
+//! Puzzle examples as tests.
+//!
+//! Each day keeps the examples from the puzzle text under `fixtures/` and
+//! lists their published answers with [`examples!`](crate::examples),
+//! which turns every fixture into one test:
+//!
+//! ```ignore
+//! aoc_core::examples! {
+//!     day10::Day10;
+//!     square_loop: "square_loop.txt", part1 = 4;
+//!     squeezed: "squeezed.txt", part2 = 4;
+//! }
+//! ```
+//!
+use crate::Solution;
+
+/// Parse `text` and compare whichever parts have a known answer.
+pub fn check_example<S: Solution>(
+    name: &str,
+    text: &str,
+    part1: Option<String>,
+    part2: Option<String>,
+) {
+    let input = match S::parse(text) {
+        Ok(input) => input,
+        Err(e) => panic!("{}: {}", name, e),
+    };
+    if let Some(want) = part1 {
+        let got = S::part1(&input).map(|a| a.to_string());
+        assert_eq!(got, Ok(want), "{} part 1", name);
+    }
+    if let Some(want) = part2 {
+        let got = S::part2(&input).map(|a| a.to_string());
+        assert_eq!(got, Ok(want), "{} part 2", name);
+    }
+}
+
+/// Generate one test per fixture file of the calling crate.
+#[macro_export]
+macro_rules! examples {
+    (
+        $solution:ty;
+        $($name:ident: $file:literal $(, part1 = $p1:expr)? $(, part2 = $p2:expr)?;)+
+    ) => {
+        $(
+            #[test]
+            fn $name() {
+                let part1: Option<String> = None;
+                $(let part1 = Some($p1.to_string());)?
+                let part2: Option<String> = None;
+                $(let part2 = Some($p2.to_string());)?
+                $crate::example::check_example::<$solution>(
+                    $file,
+                    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/", $file)),
+                    part1,
+                    part2,
+                );
+            }
+        )+
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, Solution};
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const DAY: u8 = 1;
+
+        type Input = Vec<u64>;
+        type Answer1 = u64;
+        type Answer2 = usize;
+
+        fn parse(input: &str) -> Result<Vec<u64>, Error> {
+            input
+                .split_whitespace()
+                .map(|n| n.parse().map_err(|_| Error::parse("not a number")))
+                .collect()
+        }
+
+        fn part1(input: &Vec<u64>) -> Result<u64, Error> {
+            Ok(input.iter().sum())
+        }
+
+        fn part2(input: &Vec<u64>) -> Result<usize, Error> {
+            Ok(input.len())
+        }
+    }
+
+    #[test]
+    fn test_check_example() {
+        super::check_example::<Sum>("sum", "1 2 3", Some("6".to_string()), None);
+        super::check_example::<Sum>("sum", "1 2 3", None, Some("3".to_string()));
+    }
+
+    #[test]
+    #[should_panic(expected = "sum part 1")]
+    fn test_check_example_mismatch() {
+        super::check_example::<Sum>("sum", "1 2 3", Some("7".to_string()), None);
+    }
+}
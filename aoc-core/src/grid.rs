@@ -0,0 +1,67 @@
+//! Character grids, the most common puzzle input shape.
+//!
+use crate::Error;
+
+/// A grid stored row by row, indexed as `grid[row][col]`.
+pub type Grid = Vec<Vec<char>>;
+
+pub fn parse_grid(s: &str) -> Grid {
+    s.lines().map(|l| l.chars().collect()).collect()
+}
+
+/// Like [`parse_grid`], but insist on a non-empty rectangle made only of
+/// `alphabet`, pointing at the first tile that breaks the rules.
+pub fn parse_rect(s: &str, alphabet: &str) -> Result<Grid, Error> {
+    let grid = parse_grid(s);
+    let width = match grid.first() {
+        Some(row) if !row.is_empty() => row.len(),
+        _ => return Err(Error::parse("empty grid").at_line(1)),
+    };
+    for (row_idx, row_vec) in grid.iter().enumerate() {
+        if row_vec.len() != width {
+            return Err(Error::parse(format!("row is not {} tiles wide", width))
+                .at_line(row_idx + 1)
+                .with_text(row_vec.iter().collect::<String>()));
+        }
+        if let Some(col_idx) = row_vec.iter().position(|c| !alphabet.contains(*c)) {
+            return Err(Error::parse("undefined tile")
+                .at(row_idx + 1, col_idx + 1)
+                .with_text(row_vec[col_idx]));
+        }
+    }
+    Ok(grid)
+}
+
+/// Every `(row, col)` holding `target`, in reading order.
+pub fn positions(grid: &Grid, target: char) -> impl Iterator<Item = (usize, usize)> + '_ {
+    grid.iter().enumerate().flat_map(move |(row_idx, row_vec)| {
+        row_vec
+            .iter()
+            .enumerate()
+            .filter(move |(_, c)| **c == target)
+            .map(move |(col_idx, _)| (row_idx, col_idx))
+    })
+}
+
+#[test]
+fn test_positions() {
+    let grid = parse_grid("#..\n..#\n#..");
+    assert_eq!(
+        positions(&grid, '#').collect::<Vec<_>>(),
+        vec![(0, 0), (1, 2), (2, 0)]
+    );
+    assert_eq!(positions(&grid, 'S').next(), None);
+}
+
+#[test]
+fn test_parse_rect() {
+    assert_eq!(parse_rect("#.\n.#\n", "#."), Ok(parse_grid("#.\n.#")));
+    let e = parse_rect("#.\n.x", "#.").unwrap_err();
+    assert_eq!(
+        (e.diagnostic().line, e.diagnostic().column),
+        (Some(2), Some(2))
+    );
+    let e = parse_rect("#.\n.", "#.").unwrap_err();
+    assert_eq!(e.diagnostic().line, Some(2));
+    assert!(parse_rect("", "#.").is_err());
+}
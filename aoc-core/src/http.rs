@@ -0,0 +1,175 @@
+//! Just enough HTTP to download puzzle inputs.
+//!
+//! Downloads go through [`HttpClient`], so tests can talk to a stub and
+//! the real site can be reached through `curl`, which brings TLS along.
+//!
+use std::{
+    io::{Read, Write},
+    net::TcpStream,
+    process::{Command, Stdio},
+};
+
+use crate::Error;
+
+pub trait HttpClient {
+    /// Fetch the body of `url`, failing on anything but a 200.
+    fn get(&self, url: &str, headers: &[(&str, &str)]) -> Result<String, Error>;
+}
+
+/// Plain `http://` over a TCP socket, for local servers.
+#[derive(Debug, Default)]
+pub struct TcpClient;
+
+impl HttpClient for TcpClient {
+    fn get(&self, url: &str, headers: &[(&str, &str)]) -> Result<String, Error> {
+        let failed = |why: String| Error::input(why).with_text(url);
+        let rest = url
+            .strip_prefix("http://")
+            .ok_or_else(|| failed("only http:// urls are supported".to_string()))?;
+        let (host, path) = match rest.find('/') {
+            Some(idx) => (&rest[..idx], &rest[idx..]),
+            None => (rest, "/"),
+        };
+        let mut request = format!(
+            "GET {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n",
+            path, host
+        );
+        for (name, value) in headers {
+            request += &format!("{}: {}\r\n", name, value);
+        }
+        request += "\r\n";
+
+        let mut stream = TcpStream::connect(host).map_err(|e| failed(e.to_string()))?;
+        stream
+            .write_all(request.as_bytes())
+            .map_err(|e| failed(e.to_string()))?;
+        let mut response = Vec::new();
+        stream
+            .read_to_end(&mut response)
+            .map_err(|e| failed(e.to_string()))?;
+        parse_response(&String::from_utf8_lossy(&response)).map_err(failed)
+    }
+}
+
+fn parse_response(response: &str) -> Result<String, String> {
+    let (head, body) = response
+        .split_once("\r\n\r\n")
+        .ok_or("truncated response")?;
+    let mut lines = head.lines();
+    let status = lines.next().unwrap_or_default();
+    match status.split_whitespace().nth(1) {
+        Some("200") => (),
+        _ => return Err(format!("server answered {:?}", status)),
+    }
+    let chunked = lines.any(|l| {
+        let l = l.to_ascii_lowercase();
+        l.starts_with("transfer-encoding:") && l.contains("chunked")
+    });
+    match chunked {
+        true => dechunk(body),
+        false => Ok(body.to_string()),
+    }
+}
+
+fn dechunk(mut body: &str) -> Result<String, String> {
+    let mut out = String::new();
+    loop {
+        let (size, rest) = body.split_once("\r\n").ok_or("truncated chunk")?;
+        let size = usize::from_str_radix(size.split(';').next().unwrap().trim(), 16)
+            .map_err(|_| format!("bad chunk size {:?}", size))?;
+        if size == 0 {
+            return Ok(out);
+        }
+        let chunk = rest.get(..size).ok_or("truncated chunk")?;
+        out += chunk;
+        body = rest[size..].trim_start_matches("\r\n");
+    }
+}
+
+/// Anything `curl` can reach, https included.
+#[derive(Debug, Default)]
+pub struct CurlClient;
+
+impl HttpClient for CurlClient {
+    fn get(&self, url: &str, headers: &[(&str, &str)]) -> Result<String, Error> {
+        let failed = |why: String| Error::input(why).with_text(url);
+        let mut cmd = Command::new("curl");
+        cmd.args(["--silent", "--show-error", "--fail", "--location"]);
+        // headers go through stdin, the session cookie would show in ps
+        if !headers.is_empty() {
+            cmd.args(["--header", "@-"]);
+        }
+        let mut child = cmd
+            .arg(url)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| failed(format!("cannot run curl, {}", e)))?;
+        let mut stdin = child.stdin.take().unwrap();
+        for (name, value) in headers {
+            writeln!(stdin, "{}: {}", name, value)
+                .map_err(|e| failed(format!("cannot talk to curl, {}", e)))?;
+        }
+        drop(stdin);
+        let output = child
+            .wait_with_output()
+            .map_err(|e| failed(format!("cannot run curl, {}", e)))?;
+        match output.status.success() {
+            true => Ok(String::from_utf8_lossy(&output.stdout).into_owned()),
+            false => Err(failed(format!(
+                "curl failed, {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ))),
+        }
+    }
+}
+
+#[test]
+fn test_parse_response() {
+    assert_eq!(
+        parse_response("HTTP/1.1 200 OK\r\nContent-Length: 3\r\n\r\nabc"),
+        Ok("abc".to_string())
+    );
+    assert_eq!(
+        parse_response(
+            "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n3\r\nabc\r\n2\r\nde\r\n0\r\n\r\n"
+        ),
+        Ok("abcde".to_string())
+    );
+    assert!(parse_response("HTTP/1.1 404 Not Found\r\n\r\nnope").is_err());
+    assert!(parse_response("HTTP/1.1 200 OK").is_err());
+}
+
+/// Needs `curl` on the path, and passes without it.
+#[test]
+fn test_curl_sends_headers() {
+    use std::{
+        io::{BufRead, BufReader},
+        net::TcpListener,
+        thread,
+    };
+
+    if Command::new("curl").arg("--version").output().is_err() {
+        eprintln!("skipped, cannot run curl");
+        return;
+    }
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/input", listener.local_addr().unwrap());
+    let server = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let head = BufReader::new(&stream)
+            .lines()
+            .map(|line| line.unwrap())
+            .take_while(|line| !line.is_empty())
+            .collect::<Vec<_>>();
+        write!(stream, "HTTP/1.1 200 OK\r\nContent-Length: 4\r\n\r\nbody").unwrap();
+        head
+    });
+    let headers = [("Cookie", "session=cookie"), ("User-Agent", "aoc")];
+    assert_eq!(CurlClient.get(&url, &headers), Ok("body".to_string()));
+    let head = server.join().unwrap();
+    assert_eq!(head[0], "GET /input HTTP/1.1");
+    assert!(head.contains(&"Cookie: session=cookie".to_string()));
+    assert!(head.contains(&"User-Agent: aoc".to_string()));
+}
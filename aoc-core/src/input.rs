@@ -0,0 +1,280 @@
+//! Puzzle input loading.
+//!
+//! Inputs live in a cache directory, one file per year and day, e.g.
+//! `inputs/2023/day07.in`. A missing input is downloaded when a session
+//! cookie is available, and otherwise looked up as `day07.in` in the
+//! working directory, where inputs used to be kept.
+//!
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use crate::{
+    http::{CurlClient, HttpClient, TcpClient},
+    Error,
+};
+
+pub const YEAR: u16 = 2023;
+
+/// Inputs are named after their day, e.g. `day07.in`.
+pub fn default_path(day: u8) -> PathBuf {
+    PathBuf::from(format!("day{:02}.in", day))
+}
+
+pub fn load(day: u8) -> Result<String, Error> {
+    Resolver::from_env().resolve(day, &Source::Cache)
+}
+
+/// Read an input file, naming the file in the error.
+pub fn read(path: &Path) -> Result<String, Error> {
+    std::fs::read_to_string(path).map_err(|e| {
+        Error::input(format!("cannot read, {}", e)).with_text(path.display().to_string())
+    })
+}
+
+/// Where the text of one input comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    /// The cache directory, downloading on a miss when possible.
+    Cache,
+    Path(PathBuf),
+    Stdin,
+}
+
+impl FromStr for Source {
+    type Err = String;
+
+    /// `-` is stdin, anything else a path.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "-" => Ok(Source::Stdin),
+            "" => Err("empty input path".to_string()),
+            _ => Ok(Source::Path(PathBuf::from(s))),
+        }
+    }
+}
+
+/// Downloads inputs from `base_url`, authenticated by a session cookie.
+pub struct Fetcher {
+    client: Box<dyn HttpClient>,
+    base_url: String,
+    session: String,
+}
+
+impl Fetcher {
+    pub fn new(client: Box<dyn HttpClient>, base_url: &str, session: &str) -> Fetcher {
+        Fetcher {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+        }
+    }
+
+    pub fn fetch(&self, year: u16, day: u8) -> Result<String, Error> {
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+        let cookie = format!("session={}", self.session);
+        self.client.get(&url, &[("Cookie", &cookie)])
+    }
+}
+
+pub struct Resolver {
+    cache_dir: PathBuf,
+    year: u16,
+    fetcher: Option<Fetcher>,
+}
+
+impl Resolver {
+    pub fn new(cache_dir: impl Into<PathBuf>) -> Resolver {
+        Resolver {
+            cache_dir: cache_dir.into(),
+            year: YEAR,
+            fetcher: None,
+        }
+    }
+
+    /// Configured by the environment:
+    ///
+    /// * `AOC_CACHE_DIR`, the cache directory, `inputs` by default;
+    /// * `AOC_SESSION`, the session cookie, without which nothing is
+    ///   downloaded;
+    /// * `AOC_BASE_URL`, the site to download from.
+    pub fn from_env() -> Resolver {
+        let cache_dir = std::env::var("AOC_CACHE_DIR").unwrap_or("inputs".to_string());
+        let resolver = Resolver::new(cache_dir);
+        let session = match std::env::var("AOC_SESSION") {
+            Ok(session) if !session.is_empty() => session,
+            _ => return resolver,
+        };
+        let base_url =
+            std::env::var("AOC_BASE_URL").unwrap_or("https://adventofcode.com".to_string());
+        let client: Box<dyn HttpClient> = match base_url.starts_with("http://") {
+            true => Box::new(TcpClient),
+            false => Box::new(CurlClient),
+        };
+        resolver.with_fetcher(Fetcher::new(client, &base_url, &session))
+    }
+
+    pub fn with_cache_dir(mut self, cache_dir: impl Into<PathBuf>) -> Resolver {
+        self.cache_dir = cache_dir.into();
+        self
+    }
+
+    pub fn with_year(mut self, year: u16) -> Resolver {
+        self.year = year;
+        self
+    }
+
+    pub fn with_fetcher(mut self, fetcher: Fetcher) -> Resolver {
+        self.fetcher = Some(fetcher);
+        self
+    }
+
+    pub fn cache_path(&self, day: u8) -> PathBuf {
+        self.cache_dir
+            .join(self.year.to_string())
+            .join(default_path(day))
+    }
+
+    pub fn resolve(&self, day: u8, source: &Source) -> Result<String, Error> {
+        let text = match source {
+            Source::Path(path) => read(path),
+            Source::Stdin => {
+                let mut s = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut s)
+                    .map(|_| s)
+                    .map_err(|e| Error::input(format!("cannot read stdin, {}", e)))
+            }
+            Source::Cache => self.cached(day),
+        };
+        text.map_err(|e| e.in_day(day))
+    }
+
+    fn cached(&self, day: u8) -> Result<String, Error> {
+        let path = self.cache_path(day);
+        if path.exists() {
+            return read(&path);
+        }
+        if let Some(fetcher) = &self.fetcher {
+            let s = fetcher.fetch(self.year, day)?;
+            Self::store(&path, &s)?;
+            return Ok(s);
+        }
+        let legacy = default_path(day);
+        match legacy.exists() {
+            true => read(&legacy),
+            false => Err(Error::input(
+                "no input, pass one explicitly or set AOC_SESSION to download it",
+            )
+            .with_text(path.display().to_string())),
+        }
+    }
+
+    fn store(path: &Path, s: &str) -> Result<(), Error> {
+        let failed = |e: std::io::Error| {
+            Error::input(format!("cannot cache, {}", e)).with_text(path.display().to_string())
+        };
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(failed)?;
+        }
+        std::fs::write(path, s).map_err(failed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        sync::atomic::{AtomicUsize, Ordering},
+        thread,
+    };
+
+    use super::*;
+
+    fn scratch_dir() -> PathBuf {
+        static COUNT: AtomicUsize = AtomicUsize::new(0);
+        let dir = std::env::temp_dir().join(format!(
+            "aoc-input-{}-{}",
+            std::process::id(),
+            COUNT.fetch_add(1, Ordering::SeqCst)
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    /// Serve `body` once, handing back the request head it received.
+    fn stub_server(body: &'static str) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut head = Vec::new();
+            for line in BufReader::new(&stream).lines() {
+                let line = line.unwrap();
+                if line.is_empty() {
+                    break;
+                }
+                head.push(line);
+            }
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+            head
+        });
+        (base_url, handle)
+    }
+
+    #[test]
+    fn test_default_path() {
+        assert_eq!(default_path(7), PathBuf::from("day07.in"));
+        assert_eq!(default_path(12), PathBuf::from("day12.in"));
+    }
+
+    #[test]
+    fn test_source_from_str() {
+        assert_eq!("-".parse(), Ok(Source::Stdin));
+        assert_eq!("a.in".parse(), Ok(Source::Path(PathBuf::from("a.in"))));
+    }
+
+    #[test]
+    fn test_resolve_cached() {
+        let dir = scratch_dir();
+        let resolver = Resolver::new(&dir).with_year(2022);
+        assert_eq!(resolver.cache_path(7), dir.join("2022").join("day07.in"));
+        Resolver::store(&resolver.cache_path(7), "32T3K 765\n").unwrap();
+        assert_eq!(
+            resolver.resolve(7, &Source::Cache).as_deref(),
+            Ok("32T3K 765\n")
+        );
+        let e = resolver.resolve(13, &Source::Cache).unwrap_err();
+        assert_eq!(e.diagnostic().day, Some(13));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_resolve_fetches_and_caches() {
+        let (base_url, server) = stub_server("LR\n\nAAA = (ZZZ, ZZZ)\n");
+        let dir = scratch_dir();
+        let resolver = Resolver::new(&dir).with_fetcher(Fetcher::new(
+            Box::new(TcpClient),
+            &base_url,
+            "cookie",
+        ));
+        let s = resolver.resolve(8, &Source::Cache).unwrap();
+        assert_eq!(s, "LR\n\nAAA = (ZZZ, ZZZ)\n");
+
+        let head = server.join().unwrap();
+        assert_eq!(head[0], "GET /2023/day/8/input HTTP/1.1");
+        assert!(head.contains(&"Cookie: session=cookie".to_string()));
+        // the stub is gone, so this can only come from the cache
+        assert_eq!(resolver.resolve(8, &Source::Cache), Ok(s));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
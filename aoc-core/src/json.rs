@@ -0,0 +1,26 @@
+//! Just enough JSON to write reports by hand.
+//!
+use std::fmt::Write;
+
+/// `s` as a quoted JSON string.
+pub fn string(s: &str) -> String {
+    let mut out = "\"".to_string();
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[test]
+fn test_string() {
+    assert_eq!(string("AAA"), "\"AAA\"");
+    assert_eq!(string("a\"b\\\n"), "\"a\\\"b\\\\\\u000a\"");
+}
@@ -0,0 +1,18 @@
+//! Shared plumbing for the Advent of Code 2023 solutions.
+//!
+//! Every day crate implements [`Solution`] and hands it to [`run`], which
+//! takes care of loading the puzzle input, parsing it once and printing the
+//! answers.
+//!
+pub mod args;
+mod error;
+pub mod example;
+pub mod grid;
+pub mod http;
+pub mod input;
+pub mod json;
+pub mod rng;
+mod solution;
+
+pub use error::{Diagnostic, Error};
+pub use solution::{register, run, Parsed, Part, Puzzle, Solution};
@@ -0,0 +1,65 @@
+//! A small seeded random number generator, so generated tests and games
+//! replay the same way every run.
+//!
+/// xorshift64*, plenty for shuffling cards, not for anything secret.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        // the state must never be zero
+        Rng {
+            state: (seed ^ 0x9e37_79b9_7f4a_7c15) | 1,
+        }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    /// A number in `0..n`, `n` must not be zero.
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "empty range");
+        // reject the top sliver that would favour small numbers
+        let limit = u64::MAX - u64::MAX % n;
+        loop {
+            let x = self.next_u64();
+            if x < limit {
+                return x % n;
+            }
+        }
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for idx in (1..items.len()).rev() {
+            items.swap(idx, self.below(idx as u64 + 1) as usize);
+        }
+    }
+}
+
+#[test]
+fn test_rng() {
+    let mut a = Rng::new(7);
+    let mut b = Rng::new(7);
+    let xs = (0..100).map(|_| a.below(6)).collect::<Vec<_>>();
+    assert_eq!(xs, (0..100).map(|_| b.below(6)).collect::<Vec<_>>());
+    assert!(xs.iter().all(|&x| x < 6));
+    assert!((0..6).all(|n| xs.contains(&n)));
+    assert_ne!(Rng::new(0).next_u64(), 0);
+
+    let mut v = (0..10).collect::<Vec<_>>();
+    Rng::new(1).shuffle(&mut v);
+    let mut sorted = v.clone();
+    sorted.sort();
+    assert_eq!(sorted, (0..10).collect::<Vec<_>>());
+    assert_ne!(v, sorted);
+}
@@ -0,0 +1,164 @@
+use std::{fmt, marker::PhantomData, str::FromStr};
+
+use crate::{input, Error};
+
+/// A puzzle of one day.
+///
+/// The raw text is parsed once into [`Solution::Input`], which both parts
+/// then share.
+pub trait Solution {
+    /// Day of December the puzzle was released on.
+    const DAY: u8;
+
+    type Input;
+    type Answer1: fmt::Display;
+    type Answer2: fmt::Display;
+
+    fn parse(input: &str) -> Result<Self::Input, Error>;
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Error>;
+
+    /// Days that only solved the first half keep the default.
+    fn part2(_input: &Self::Input) -> Result<Self::Answer2, Error> {
+        Err(Error::solve("part two is not solved yet"))
+    }
+}
+
+/// Load the input of `S` from the working directory and print its answers,
+/// exiting with a diagnostic when anything fails.
+pub fn run<S: Solution>() {
+    if let Err(e) = try_run::<S>() {
+        eprintln!("{}", e.in_day(S::DAY));
+        std::process::exit(1);
+    }
+}
+
+fn try_run<S: Solution>() -> Result<(), Error> {
+    let input = S::parse(&input::load(S::DAY)?)?;
+    println!("{}", S::part1(&input).map_err(|e| e.in_part(Part::One))?);
+    println!("{}", S::part2(&input).map_err(|e| e.in_part(Part::Two))?);
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("part must be 1 or 2, got {:?}", s)),
+        }
+    }
+}
+
+/// Object safe face of a [`Solution`], so that a runner can keep every day
+/// in one list and dispatch on the day number.
+pub trait Puzzle {
+    fn day(&self) -> u8;
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Parsed>, Error>;
+}
+
+/// An input already parsed by its [`Puzzle`], ready to answer either part.
+pub trait Parsed {
+    fn solve(&self, part: Part) -> Result<String, Error>;
+}
+
+struct Registered<S>(PhantomData<S>);
+
+struct ParsedInput<S: Solution>(S::Input);
+
+impl<S: Solution + 'static> Puzzle for Registered<S> {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Parsed>, Error> {
+        match S::parse(input) {
+            Ok(input) => Ok(Box::new(ParsedInput::<S>(input))),
+            Err(e) => Err(e.in_day(S::DAY)),
+        }
+    }
+}
+
+impl<S: Solution> Parsed for ParsedInput<S> {
+    fn solve(&self, part: Part) -> Result<String, Error> {
+        let answer = match part {
+            Part::One => S::part1(&self.0).map(|a| a.to_string()),
+            Part::Two => S::part2(&self.0).map(|a| a.to_string()),
+        };
+        answer.map_err(|e| e.in_day(S::DAY).in_part(part))
+    }
+}
+
+pub fn register<S: Solution + 'static>() -> Box<dyn Puzzle> {
+    Box::new(Registered::<S>(PhantomData))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Length;
+
+    impl Solution for Length {
+        const DAY: u8 = 1;
+
+        type Input = Vec<usize>;
+        type Answer1 = usize;
+        type Answer2 = usize;
+
+        fn parse(input: &str) -> Result<Vec<usize>, Error> {
+            match input.is_empty() {
+                true => Err(Error::parse("empty")),
+                false => Ok(input.lines().map(str::len).collect()),
+            }
+        }
+
+        fn part1(input: &Vec<usize>) -> Result<usize, Error> {
+            Ok(input.iter().sum())
+        }
+    }
+
+    #[test]
+    fn test_register() {
+        let p = register::<Length>();
+        assert_eq!(p.day(), 1);
+        let parsed = p.parse("ab\ncde").unwrap();
+        assert_eq!(parsed.solve(Part::One).as_deref(), Ok("5"));
+        assert_eq!(
+            parsed.solve(Part::Two).unwrap_err().diagnostic().day,
+            Some(1)
+        );
+        match p.parse("") {
+            Err(e) => assert_eq!(e.diagnostic().day, Some(1)),
+            Ok(_) => panic!("empty input should not parse"),
+        }
+    }
+
+    #[test]
+    fn test_parse_part() {
+        assert_eq!("2".parse::<Part>(), Ok(Part::Two));
+        assert!("3".parse::<Part>().is_err());
+    }
+}
@@ -0,0 +1,130 @@
+//! Known answers for our own inputs, kept in a small TOML file:
+//!
+//! ```toml
+//! [day10]
+//! part1 = 6828
+//! part2 = "459"
+//! ```
+//!
+//! Only tables named `dayNN` and the keys `part1` and `part2` are
+//! understood; values are integers or double quoted strings.
+//!
+use std::{collections::BTreeMap, path::Path};
+
+use aoc_core::{input, Error, Part};
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    known: BTreeMap<(u8, Part), String>,
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Answers, Error> {
+        Self::parse(&input::read(path)?)
+    }
+
+    pub fn parse(s: &str) -> Result<Answers, Error> {
+        let mut answers = Answers::default();
+        let mut day = None;
+        for (line, line_idx) in s.lines().zip(1..) {
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(table) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                day = Some(parse_table(table.trim()).map_err(|e| e.at_line(line_idx))?);
+                continue;
+            }
+            let (key, value) = match line.split_once('=') {
+                Some((k, v)) => (k.trim(), v.trim()),
+                None => {
+                    return Err(Error::parse("expected `key = value`")
+                        .at_line(line_idx)
+                        .with_text(line))
+                }
+            };
+            let part = match key {
+                "part1" => Part::One,
+                "part2" => Part::Two,
+                _ => return Err(Error::parse("unknown key").at_line(line_idx).with_text(key)),
+            };
+            let day = match day {
+                Some(day) => day,
+                None => {
+                    return Err(Error::parse("answer outside of a [dayNN] table")
+                        .at_line(line_idx)
+                        .with_text(line))
+                }
+            };
+            let value = parse_value(value).map_err(|e| e.at_line(line_idx))?;
+            answers.known.insert((day, part), value);
+        }
+        Ok(answers)
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<&str> {
+        self.known.get(&(day, part)).map(String::as_str)
+    }
+}
+
+/// Drop a `#` comment, unless the `#` sits inside a string.
+fn strip_comment(line: &str) -> &str {
+    let mut quoted = false;
+    for (idx, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '#' if !quoted => return &line[..idx],
+            _ => continue,
+        }
+    }
+    line
+}
+
+fn parse_table(table: &str) -> Result<u8, Error> {
+    table
+        .strip_prefix("day")
+        .and_then(|d| d.parse().ok())
+        .ok_or(Error::parse("tables are named dayNN").with_text(table))
+}
+
+fn parse_value(value: &str) -> Result<String, Error> {
+    if let Some(quoted) = value.strip_prefix('"') {
+        return match quoted.strip_suffix('"') {
+            Some(s) if !s.contains('"') => Ok(s.to_string()),
+            _ => Err(Error::parse("unterminated string").with_text(value)),
+        };
+    }
+    match value.parse::<i128>() {
+        Ok(n) => Ok(n.to_string()),
+        Err(_) => Err(Error::parse("answers are integers or strings").with_text(value)),
+    }
+}
+
+#[test]
+fn test_parse_answers() {
+    let answers = Answers::parse(
+        "# our answers
+[day07]
+part1 = 248812215
+
+[ day10 ]
+part1 = \"6828\"  # quoted
+part2 = -3
+",
+    )
+    .unwrap();
+    assert_eq!(answers.get(7, Part::One), Some("248812215"));
+    assert_eq!(answers.get(7, Part::Two), None);
+    assert_eq!(answers.get(10, Part::One), Some("6828"));
+    assert_eq!(answers.get(10, Part::Two), Some("-3"));
+}
+
+#[test]
+fn test_parse_answers_errors() {
+    let e = Answers::parse("[day07]\npart3 = 1").unwrap_err();
+    assert_eq!(e.diagnostic().line, Some(2));
+    assert!(Answers::parse("part1 = 1").is_err());
+    assert!(Answers::parse("[seven]").is_err());
+    assert!(Answers::parse("[day07]\npart1 = one").is_err());
+    assert!(Answers::parse("[day07]\npart1 = \"1").is_err());
+}
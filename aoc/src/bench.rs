@@ -0,0 +1,250 @@
+//! Repeated timing of every parse and part, with a stored baseline to
+//! catch regressions.
+//!
+//! Baselines are plain text, one `day stage median_ns` line per
+//! measurement, where stage is `parse`, `1` or `2`.
+//!
+use std::{
+    collections::BTreeMap,
+    fmt,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use aoc_core::{args::Args, input, Error, Parsed, Puzzle};
+
+use crate::{Inputs, Selection};
+
+pub fn cmd_bench(mut args: Args) -> Result<(), String> {
+    let selection = Selection::from_args(&mut args)?;
+    let inputs = Inputs::from_args(&mut args, &selection)?;
+    let runs = args.parsed::<usize>("--runs")?.unwrap_or(10);
+    let save = args.value("--save")?.map(PathBuf::from);
+    let baseline = match args.value("--baseline")? {
+        Some(path) => Some(Baseline::load(Path::new(&path))?),
+        None => None,
+    };
+    let threshold = args.parsed::<f64>("--threshold")?.unwrap_or(10.0);
+    args.finish()?;
+    if runs == 0 {
+        return Err("--runs must be positive".to_string());
+    }
+    let Selection { puzzles, parts } = selection;
+
+    let mut measured = Baseline::default();
+    let mut regressions = 0;
+    let mut failures = 0;
+    for puzzle in puzzles {
+        let s = match inputs.read(puzzle.day()) {
+            Ok(s) => s,
+            Err(e) => {
+                failures += 1;
+                eprintln!("{}", e);
+                continue;
+            }
+        };
+        let mut stages: Vec<(String, Stats)> = Vec::new();
+        match measure_parse(puzzle.as_ref(), &s, runs) {
+            Ok((parsed, stats)) => {
+                stages.push(("parse".to_string(), stats));
+                for &part in &parts {
+                    match measure(runs, || parsed.solve(part)) {
+                        Ok(stats) => stages.push((part.to_string(), stats)),
+                        Err(e) => {
+                            failures += 1;
+                            eprintln!("{}", e);
+                        }
+                    }
+                }
+            }
+            Err(e) => {
+                failures += 1;
+                eprintln!("{}", e);
+            }
+        }
+        for (stage, stats) in stages {
+            let label = format!("day {:02} {:<5}", puzzle.day(), stage);
+            let verdict = match baseline.as_ref().and_then(|b| b.get(puzzle.day(), &stage)) {
+                Some(before) => {
+                    let change = percent_change(before, stats.median);
+                    match change > threshold {
+                        true => {
+                            regressions += 1;
+                            format!("  {:+.1}% vs baseline, REGRESSION", change)
+                        }
+                        false => format!("  {:+.1}% vs baseline", change),
+                    }
+                }
+                None => String::new(),
+            };
+            println!("{} {}{}", label, stats, verdict);
+            measured.insert(puzzle.day(), &stage, stats.median);
+        }
+    }
+
+    if let Some(path) = save {
+        std::fs::write(&path, measured.to_string())
+            .map_err(|e| format!("cannot write {}: {}", path.display(), e))?;
+    }
+    match (failures, regressions) {
+        (0, 0) => Ok(()),
+        (0, n) => Err(format!("{} stage(s) regressed more than {}%", n, threshold)),
+        (n, _) => Err(format!("{} stage(s) failed", n)),
+    }
+}
+
+fn measure_parse(
+    puzzle: &dyn Puzzle,
+    s: &str,
+    runs: usize,
+) -> Result<(Box<dyn Parsed>, Stats), Error> {
+    let stats = measure(runs, || puzzle.parse(s))?;
+    Ok((puzzle.parse(s)?, stats))
+}
+
+/// Time `runs` calls of `f` after one warm up call.
+fn measure<T>(runs: usize, mut f: impl FnMut() -> Result<T, Error>) -> Result<Stats, Error> {
+    f()?;
+    let mut samples = Vec::with_capacity(runs);
+    for _ in 0..runs {
+        let start = Instant::now();
+        f()?;
+        samples.push(start.elapsed());
+    }
+    Ok(Stats::new(samples))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub mean: Duration,
+    pub median: Duration,
+    pub stddev: Duration,
+    pub min: Duration,
+}
+
+impl Stats {
+    pub fn new(mut samples: Vec<Duration>) -> Stats {
+        assert!(!samples.is_empty(), "no samples");
+        samples.sort();
+        let n = samples.len();
+        let median = match n % 2 {
+            1 => samples[n / 2],
+            _ => (samples[n / 2 - 1] + samples[n / 2]) / 2,
+        };
+        let secs = samples
+            .iter()
+            .map(Duration::as_secs_f64)
+            .collect::<Vec<_>>();
+        let mean = secs.iter().sum::<f64>() / n as f64;
+        let variance = secs.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / n as f64;
+        Stats {
+            mean: Duration::from_secs_f64(mean),
+            median,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+            min: samples[0],
+        }
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "mean {:>10.3?}  median {:>10.3?}  stddev {:>10.3?}  min {:>10.3?}",
+            self.mean, self.median, self.stddev, self.min
+        )
+    }
+}
+
+fn percent_change(before: Duration, after: Duration) -> f64 {
+    let before = before.as_secs_f64().max(f64::MIN_POSITIVE);
+    (after.as_secs_f64() - before) / before * 100.0
+}
+
+/// Median timings keyed by day and stage.
+#[derive(Debug, Default, PartialEq)]
+struct Baseline {
+    medians: BTreeMap<(u8, String), Duration>,
+}
+
+impl Baseline {
+    fn load(path: &Path) -> Result<Baseline, String> {
+        let s = input::read(path).map_err(|e| e.to_string())?;
+        Self::parse(&s).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    fn parse(s: &str) -> Result<Baseline, Error> {
+        let mut baseline = Baseline::default();
+        for (line, line_idx) in s.lines().zip(1..) {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            let (day, nanos) = match fields[..] {
+                [day, _, nanos] => (day.parse::<u8>(), nanos.parse::<u64>()),
+                _ => {
+                    return Err(Error::parse("expected `day stage median_ns`")
+                        .at_line(line_idx)
+                        .with_text(line))
+                }
+            };
+            match (day, nanos) {
+                (Ok(day), Ok(nanos)) => {
+                    baseline.insert(day, fields[1], Duration::from_nanos(nanos))
+                }
+                _ => {
+                    return Err(Error::parse("day and median must be numbers")
+                        .at_line(line_idx)
+                        .with_text(line))
+                }
+            }
+        }
+        Ok(baseline)
+    }
+
+    fn insert(&mut self, day: u8, stage: &str, median: Duration) {
+        self.medians.insert((day, stage.to_string()), median);
+    }
+
+    fn get(&self, day: u8, stage: &str) -> Option<Duration> {
+        self.medians.get(&(day, stage.to_string())).copied()
+    }
+}
+
+impl fmt::Display for Baseline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# day stage median_ns")?;
+        for ((day, stage), median) in &self.medians {
+            writeln!(f, "{} {} {}", day, stage, median.as_nanos())?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_stats() {
+    let ms = Duration::from_millis;
+    let stats = Stats::new(vec![ms(4), ms(2), ms(6), ms(8)]);
+    assert_eq!(stats.median, ms(5));
+    assert_eq!(stats.min, ms(2));
+    assert!((stats.mean.as_secs_f64() - 0.005).abs() < 1e-9);
+    assert!((stats.stddev.as_secs_f64() - 5f64.sqrt() / 1000.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_baseline_round_trip() {
+    let mut baseline = Baseline::default();
+    baseline.insert(10, "parse", Duration::from_nanos(1500));
+    baseline.insert(10, "2", Duration::from_nanos(42));
+    let parsed = Baseline::parse(&baseline.to_string()).unwrap();
+    assert_eq!(parsed, baseline);
+    assert_eq!(parsed.get(10, "2"), Some(Duration::from_nanos(42)));
+    assert!(Baseline::parse("10 parse").is_err());
+}
+
+#[test]
+fn test_percent_change() {
+    let before = Duration::from_micros(100);
+    assert!((percent_change(before, Duration::from_micros(120)) - 20.0).abs() < 1e-9);
+}
@@ -0,0 +1,98 @@
+//! One runner for every day.
+//!
+//! ```text
+//! aoc run [--day N] [--part 1|2] [INPUT OPTIONS]
+//! aoc verify [--day N] [--part 1|2] [--answers PATH] [INPUT OPTIONS]
+//! aoc bench [--day N] [--part 1|2] [--runs N] [--save PATH] [--baseline PATH]
+//!           [INPUT OPTIONS]
+//! ```
+//!
+//! Without `--day` every registered day runs in sequence, each reading its
+//! input from the cache directory, see [`aoc_core::input`]. `--input PATH`
+//! reads one day's input from a file instead, or from stdin when `PATH` is
+//! `-`, and `--cache DIR` overrides the cache directory. `verify`
+//! compares the answers with a manifest of known ones, `answers.toml`
+//! unless told otherwise, and exits with an error on any mismatch. `bench`
+//! reports timing statistics and can save or compare against a baseline.
+//!
+use std::process::ExitCode;
+
+use aoc_core::{
+    args::Args,
+    input::{Resolver, Source},
+    Error, Part, Puzzle,
+};
+
+mod answers;
+mod bench;
+mod registry;
+mod run;
+mod verify;
+
+const USAGE: &str = "usage: aoc run [--day N] [--part 1|2] [INPUT OPTIONS]
+       aoc verify [--day N] [--part 1|2] [--answers PATH] [INPUT OPTIONS]
+       aoc bench [--day N] [--part 1|2] [--runs N] [--save PATH] [--baseline PATH]
+                 [--threshold PERCENT] [INPUT OPTIONS]
+input options: [--input PATH|-] [--cache DIR]";
+
+fn main() -> ExitCode {
+    let mut args = Args::from_env();
+    let result = match args.subcommand().as_deref() {
+        Some("run") => run::cmd_run(args),
+        Some("verify") => verify::cmd_verify(args),
+        Some("bench") => bench::cmd_bench(args),
+        Some(other) => Err(format!("unknown command {:?}\n{}", other, USAGE)),
+        None => Err(USAGE.to_string()),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Days and parts picked by `--day` and `--part`.
+struct Selection {
+    puzzles: Vec<Box<dyn Puzzle>>,
+    parts: Vec<Part>,
+}
+
+impl Selection {
+    fn from_args(args: &mut Args) -> Result<Selection, String> {
+        let puzzles = match args.parsed::<u8>("--day")? {
+            Some(day) => vec![registry::find(day).ok_or(format!("day {} is not solved", day))?],
+            None => registry::puzzles(),
+        };
+        let parts = match args.parsed::<Part>("--part")? {
+            Some(part) => vec![part],
+            None => Part::ALL.to_vec(),
+        };
+        Ok(Selection { puzzles, parts })
+    }
+}
+
+/// Where inputs come from, picked by `--input` and `--cache`.
+struct Inputs {
+    resolver: Resolver,
+    source: Source,
+}
+
+impl Inputs {
+    fn from_args(args: &mut Args, selection: &Selection) -> Result<Inputs, String> {
+        let source = args.parsed::<Source>("--input")?.unwrap_or(Source::Cache);
+        if source != Source::Cache && selection.puzzles.len() != 1 {
+            return Err("--input needs --day".to_string());
+        }
+        let mut resolver = Resolver::from_env();
+        if let Some(dir) = args.value("--cache")? {
+            resolver = resolver.with_cache_dir(dir);
+        }
+        Ok(Inputs { resolver, source })
+    }
+
+    fn read(&self, day: u8) -> Result<String, Error> {
+        self.resolver.resolve(day, &self.source)
+    }
+}
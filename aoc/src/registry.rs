@@ -0,0 +1,22 @@
+use aoc_core::{register, Puzzle};
+
+/// Every solved day, in calendar order.
+pub fn puzzles() -> Vec<Box<dyn Puzzle>> {
+    vec![
+        register::<day07::Day07>(),
+        register::<day08::Day08>(),
+        register::<day10::Day10>(),
+        register::<day11::Day11>(),
+        register::<day12::Day12>(),
+    ]
+}
+
+pub fn find(day: u8) -> Option<Box<dyn Puzzle>> {
+    puzzles().into_iter().find(|p| p.day() == day)
+}
+
+#[test]
+fn test_days_are_unique_and_sorted() {
+    let days = puzzles().iter().map(|p| p.day()).collect::<Vec<_>>();
+    assert!(days.windows(2).all(|w| w[0] < w[1]));
+}
@@ -0,0 +1,56 @@
+use std::time::Instant;
+
+use aoc_core::args::Args;
+
+use crate::{Inputs, Selection};
+
+pub fn cmd_run(mut args: Args) -> Result<(), String> {
+    let selection = Selection::from_args(&mut args)?;
+    let inputs = Inputs::from_args(&mut args, &selection)?;
+    args.finish()?;
+    let Selection { puzzles, parts } = selection;
+
+    let mut failures = 0;
+    for puzzle in puzzles {
+        let s = match inputs.read(puzzle.day()) {
+            Ok(s) => s,
+            Err(e) => {
+                failures += parts.len();
+                eprintln!("{}", e);
+                continue;
+            }
+        };
+        let start = Instant::now();
+        let parsed = match puzzle.parse(&s) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                failures += parts.len();
+                eprintln!("{}", e);
+                continue;
+            }
+        };
+        println!("day {:02} parse  ({:.3?})", puzzle.day(), start.elapsed());
+        for &part in &parts {
+            let start = Instant::now();
+            let answer = parsed.solve(part);
+            let elapsed = start.elapsed();
+            match answer {
+                Ok(answer) => println!(
+                    "day {:02} part {}: {:<20} ({:.3?})",
+                    puzzle.day(),
+                    part,
+                    answer,
+                    elapsed
+                ),
+                Err(e) => {
+                    failures += 1;
+                    eprintln!("{}", e);
+                }
+            }
+        }
+    }
+    match failures {
+        0 => Ok(()),
+        n => Err(format!("{} part(s) failed", n)),
+    }
+}
@@ -0,0 +1,85 @@
+use std::path::PathBuf;
+
+use aoc_core::{args::Args, Error, Part, Puzzle};
+
+use crate::{answers::Answers, Inputs, Selection};
+
+/// Run every selected day against its input and compare with the answers
+/// manifest, failing when any part disagrees or errors.
+pub fn cmd_verify(mut args: Args) -> Result<(), String> {
+    let selection = Selection::from_args(&mut args)?;
+    let inputs = Inputs::from_args(&mut args, &selection)?;
+    let manifest = args
+        .value("--answers")?
+        .map_or(PathBuf::from("answers.toml"), PathBuf::from);
+    args.finish()?;
+    let Selection { puzzles, parts } = selection;
+    let answers = Answers::load(&manifest).map_err(|e| e.to_string())?;
+
+    let mut failures = 0;
+    for puzzle in puzzles {
+        let expected = parts
+            .iter()
+            .filter_map(|&part| Some((part, answers.get(puzzle.day(), part)?)))
+            .collect::<Vec<_>>();
+        if expected.is_empty() {
+            println!("day {:02}: skipped, no known answers", puzzle.day());
+            continue;
+        }
+        for (part, outcome) in check(puzzle.as_ref(), &inputs, &expected) {
+            match outcome {
+                Ok(()) => println!("day {:02} part {}: ok", puzzle.day(), part),
+                Err(why) => {
+                    failures += 1;
+                    println!("day {:02} part {}: FAIL, {}", puzzle.day(), part, why);
+                }
+            }
+        }
+    }
+    match failures {
+        0 => Ok(()),
+        n => Err(format!("{} part(s) failed verification", n)),
+    }
+}
+
+fn check(
+    puzzle: &dyn Puzzle,
+    inputs: &Inputs,
+    expected: &[(Part, &str)],
+) -> Vec<(Part, Result<(), String>)> {
+    let failed_all = |e: Error| {
+        expected
+            .iter()
+            .map(|&(part, _)| (part, Err(e.to_string())))
+            .collect()
+    };
+    let parsed = match inputs.read(puzzle.day()).and_then(|s| puzzle.parse(&s)) {
+        Ok(parsed) => parsed,
+        Err(e) => return failed_all(e),
+    };
+    expected
+        .iter()
+        .map(|&(part, want)| {
+            let outcome = match parsed.solve(part) {
+                Ok(got) if got == want => Ok(()),
+                Ok(got) => Err(diff(want, &got)),
+                Err(e) => Err(e.to_string()),
+            };
+            (part, outcome)
+        })
+        .collect()
+}
+
+/// Describe a wrong answer, with the distance when both are numbers.
+fn diff(want: &str, got: &str) -> String {
+    match (want.parse::<i128>(), got.parse::<i128>()) {
+        (Ok(w), Ok(g)) => format!("expected {}, got {} ({:+})", want, got, g - w),
+        _ => format!("expected {:?}, got {:?}", want, got),
+    }
+}
+
+#[test]
+fn test_diff() {
+    assert_eq!(diff("374", "370"), "expected 374, got 370 (-4)");
+    assert_eq!(diff("abc", "abd"), "expected \"abc\", got \"abd\"");
+}
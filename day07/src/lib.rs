@@ -0,0 +1,475 @@
+//! https://adventofcode.com/2023/day/7
+//!
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    str::FromStr,
+};
+
+use aoc_core::{Error, Solution};
+
+pub mod odds;
+pub mod poker;
+pub mod report;
+mod rules;
+pub mod tournament;
+
+pub use rules::{Classifier, Ruleset};
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+
+    type Input = Vec<(Hand, u64)>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        parse_inputs(input, &Ruleset::jacks())
+    }
+
+    fn part1(sets: &Self::Input) -> Result<u64, Error> {
+        Ok(winnings(sets))
+    }
+
+    fn part2(sets: &Self::Input) -> Result<u64, Error> {
+        calculate_bids(sets.clone(), &Ruleset::jokers())
+    }
+}
+
+/// A card's strength, its position in the [`Ruleset`] alphabet.
+type Card = u32;
+
+/// Kinds of hands, from the weakest to the strongest.
+///
+/// Camel Cards never makes straights or flushes, only [`poker`] does, so
+/// they sit where poker ranks them without changing the Camel Cards order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HandType {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    Straight,
+    Flush,
+    FullHouse,
+    FourOfAKind,
+    StraightFlush,
+    FiveOfAKind,
+}
+
+impl fmt::Display for HandType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            HandType::HighCard => "high card",
+            HandType::OnePair => "one pair",
+            HandType::TwoPair => "two pair",
+            HandType::ThreeOfAKind => "three of a kind",
+            HandType::Straight => "straight",
+            HandType::Flush => "flush",
+            HandType::FullHouse => "full house",
+            HandType::FourOfAKind => "four of a kind",
+            HandType::StraightFlush => "straight flush",
+            HandType::FiveOfAKind => "five of a kind",
+        };
+        f.pad(name)
+    }
+}
+
+#[test]
+fn test_detect() {
+    use HandType::*;
+    let strs = ["32T3K", "T55J5", "KK677", "KTJJT", "QQQJA"];
+    let types = [OnePair, ThreeOfAKind, TwoPair, TwoPair, ThreeOfAKind];
+
+    for (s, t) in strs.into_iter().zip(types) {
+        assert_eq!(s.parse::<Hand>().unwrap().hand_type(), t, "{}", s);
+    }
+    let detect = |s: &str| s.parse::<Hand>().unwrap().hand_type();
+    assert_eq!(detect("23456"), HighCard);
+    assert_eq!(detect("23332"), FullHouse);
+    assert_eq!(detect("AA8AA"), FourOfAKind);
+    assert_eq!(detect("AAAAA"), FiveOfAKind);
+}
+
+#[test]
+fn test_detect_with_jokers() {
+    use HandType::*;
+    let strs = [
+        "32T3K", "T55J5", "KK677", "KTJJT", "QQQJA", "JJJJJ", "2345J", "J2J3J",
+    ];
+    let types = [
+        OnePair,
+        FourOfAKind,
+        TwoPair,
+        FourOfAKind,
+        FourOfAKind,
+        FiveOfAKind,
+        OnePair,
+        FourOfAKind,
+    ];
+
+    let jokers = Ruleset::jokers();
+    for (s, t) in strs.into_iter().zip(types) {
+        assert_eq!(Hand::parse(s, &jokers).unwrap().hand_type(), t, "{}", s);
+    }
+    // a joker loses every tie break, even against a 2
+    let joker = Hand::parse("JKKK2", &jokers).unwrap();
+    let queen = Hand::parse("QQQQ2", &jokers).unwrap();
+    assert!(joker < queen);
+}
+
+/// Cards and their type, worked out once when the hand is made.
+#[derive(Debug, Clone)]
+pub struct Hand {
+    faces: String,
+    cards: Vec<Card>,
+    hand_type: HandType,
+    /// Orders like the hand itself among hands of the same rules.
+    key: u64,
+    /// The card every wildcard in the hand stands in for.
+    wildcard_as: Option<char>,
+}
+
+impl Hand {
+    /// Read a hand under `rules`, errors point at the offending card by
+    /// column.
+    pub fn parse(s: &str, rules: &Ruleset) -> Result<Hand, Error> {
+        let cards = rules.cards(s)?;
+        let hand_type = rules.classify(&cards);
+        Ok(Hand {
+            faces: s.to_string(),
+            key: rules.key(hand_type, &cards),
+            wildcard_as: rules.wildcard_as(&cards),
+            hand_type,
+            cards,
+        })
+    }
+
+    /// The same cards, played under `rules`.
+    pub fn with_rules(&self, rules: &Ruleset) -> Result<Hand, Error> {
+        Hand::parse(&self.faces, rules)
+    }
+
+    pub fn hand_type(&self) -> HandType {
+        self.hand_type
+    }
+
+    /// The hand packed into one integer that sorts like it, only
+    /// comparable with hands of the same rules.
+    pub fn key(&self) -> u64 {
+        self.key
+    }
+
+    pub fn wildcard_as(&self) -> Option<char> {
+        self.wildcard_as
+    }
+}
+
+/// Hands are parsed with jacks, see [`Hand::parse`] for other rules.
+impl FromStr for Hand {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Hand, Error> {
+        Hand::parse(s, &Ruleset::jacks())
+    }
+}
+
+impl fmt::Display for Hand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&self.faces)
+    }
+}
+
+/// Stronger type first, then card by card from the left.
+impl Ord for Hand {
+    fn cmp(&self, other: &Hand) -> Ordering {
+        if self.hand_type > other.hand_type {
+            return Ordering::Greater;
+        } else if other.hand_type > self.hand_type {
+            return Ordering::Less;
+        }
+        let mut result = Ordering::Equal;
+        for (c1, c2) in self.cards.iter().zip(&other.cards) {
+            match c1.cmp(c2) {
+                o @ Ordering::Less | o @ Ordering::Greater => {
+                    result = o;
+                    break;
+                }
+                _ => continue,
+            }
+        }
+        result
+    }
+}
+
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Hand) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Equal when neither is stronger, even poker hands in other suits.
+impl PartialEq for Hand {
+    fn eq(&self, other: &Hand) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Hand {}
+
+impl Hash for Hand {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.hand_type.hash(state);
+        self.cards.hash(state);
+    }
+}
+
+#[test]
+fn test_hand() {
+    let hand = |s: &str| s.parse::<Hand>().unwrap();
+    assert_eq!(hand("T55J5").cards, [8, 3, 3, 9, 3]);
+    assert_eq!(hand("T55J5").to_string(), "T55J5");
+    let jokers = Ruleset::jokers();
+    assert_eq!(
+        hand("T55J5").with_rules(&jokers).unwrap().cards,
+        [9, 4, 4, 0, 4]
+    );
+    assert!(hand("KK677") > hand("KTJJT"));
+    assert!(hand("33332") > hand("2AAAA"));
+    let e = "32X3K".parse::<Hand>().unwrap_err();
+    assert_eq!(e.diagnostic().column, Some(3));
+    assert_eq!(e.diagnostic().text.as_deref(), Some("X"));
+    assert!("32T3".parse::<Hand>().is_err());
+    assert_eq!(format!("{:<10}|", HandType::OnePair), "one pair  |");
+}
+
+/// Rank every hand under `rules` and total up rank times bid.
+pub fn calculate_bids(sets: Vec<(Hand, u64)>, rules: &Ruleset) -> Result<u64, Error> {
+    let sets = sets
+        .into_iter()
+        .map(|(hand, bid)| Ok((hand.with_rules(rules)?, bid)))
+        .collect::<Result<Vec<_>, Error>>()?;
+    Ok(winnings(&sets))
+}
+
+/// Total of rank times bid, hands ranked as they are.
+pub fn winnings(sets: &[(Hand, u64)]) -> u64 {
+    ranks(sets.iter().map(|s| &s.0))
+        .into_iter()
+        .zip(sets)
+        .fold(0, |acc, (rank, (_, bid))| acc + rank * bid)
+}
+
+/// The rank of each hand, from 1 for the weakest, in the order given.
+/// Equal hands rank in that order too.
+pub fn ranks<'a>(hands: impl IntoIterator<Item = &'a Hand>) -> Vec<u64> {
+    let mut keyed = hands
+        .into_iter()
+        .zip(0..)
+        .map(|(hand, idx)| (hand.key, idx))
+        .collect::<Vec<(u64, usize)>>();
+    radix_sort(&mut keyed);
+    let mut ranks = vec![0; keyed.len()];
+    for ((_, idx), rank) in keyed.into_iter().zip(1..) {
+        ranks[idx] = rank;
+    }
+    ranks
+}
+
+/// `hand_type` above `bits` per card, first card highest.
+fn pack_key(hand_type: HandType, cards: &[Card], bits: u32) -> u64 {
+    cards
+        .iter()
+        .fold(hand_type as u64, |key, &c| key << bits | c as u64)
+}
+
+/// Stable sort by key, a byte at a time from the lowest, skipping the
+/// bytes no key uses.
+pub fn radix_sort<T: Copy>(items: &mut Vec<(u64, T)>) {
+    let max = items.iter().map(|a| a.0).max().unwrap_or(0);
+    let passes = (u64::BITS - max.leading_zeros()).div_ceil(8);
+    let mut buffer = items.clone();
+    for pass in 0..passes {
+        let shift = pass * 8;
+        let digit = |key: u64| (key >> shift) as usize & 0xff;
+        let mut starts = [0; 257];
+        for a in items.iter() {
+            starts[digit(a.0) + 1] += 1;
+        }
+        for idx in 1..starts.len() {
+            starts[idx] += starts[idx - 1];
+        }
+        for &a in items.iter() {
+            let slot = &mut starts[digit(a.0)];
+            buffer[*slot] = a;
+            *slot += 1;
+        }
+        std::mem::swap(items, &mut buffer);
+    }
+}
+
+#[test]
+fn test_keys() {
+    let hand = |s: &str| s.parse::<Hand>().unwrap();
+    assert_eq!(hand("32T3K").key(), 1 << 20 | 0x1081b);
+    assert!(hand("KK677").key() > hand("KTJJT").key());
+    assert!(hand("33332").key() > hand("2AAAA").key());
+    let mut v = vec![(3, 'c'), (1 << 40, 'd'), (1, 'a'), (3, 'b'), (0, 'z')];
+    radix_sort(&mut v);
+    assert_eq!(v, [(0, 'z'), (1, 'a'), (3, 'c'), (3, 'b'), (1 << 40, 'd')]);
+}
+
+#[test]
+fn test_rank_many() {
+    let mut rng = aoc_core::rng::Rng::new(2023);
+    for rules in [Ruleset::jacks(), Ruleset::jokers()] {
+        let sets = (0..20_000)
+            .map(|_| (rules.random_hand(&mut rng), rng.below(1000)))
+            .collect::<Vec<_>>();
+        let mut sorted = sets.clone();
+        sorted.sort_by(|a, b| a.0.cmp(&b.0));
+        let expected = sorted
+            .iter()
+            .zip(1..)
+            .fold(0, |acc, (a, rank)| acc + rank * a.1);
+        assert_eq!(winnings(&sets), expected);
+    }
+}
+
+#[test]
+fn test_calculate_bids() {
+    let strs = ["32T3K", "T55J5", "KK677", "KTJJT", "QQQJA"];
+    let bids = [765, 684, 28, 220, 483];
+    let mut v = Vec::new();
+    for (s, i) in strs.into_iter().zip(bids) {
+        v.push((s.parse().unwrap(), i))
+    }
+    assert_eq!(calculate_bids(v.clone(), &Ruleset::jacks()), Ok(6440));
+    assert_eq!(calculate_bids(v.clone(), &Ruleset::jokers()), Ok(5905));
+    let hands = v.iter().map(|s| s.0.clone()).collect::<Vec<Hand>>();
+    assert_eq!(ranks(&hands), [1, 4, 3, 2, 5]);
+    // ties rank in the order given
+    assert_eq!(ranks(&[hands[2].clone(), hands[2].clone()]), [1, 2]);
+    // without types the strongest first card wins
+    let plain = Ruleset::jacks().with_classifier(Classifier::Plain);
+    assert_eq!(
+        calculate_bids(v.clone(), &plain),
+        Ok(765 + 684 * 2 + 220 * 4 + 28 * 5 + 483 * 3)
+    );
+    let short = Ruleset::jacks().with_hand_len(4).unwrap();
+    assert!(calculate_bids(v, &short).is_err());
+}
+
+pub fn parse_inputs(v: &str, rules: &Ruleset) -> Result<Vec<(Hand, u64)>, Error> {
+    parse_lines(v, |s| Hand::parse(s, rules))
+}
+
+/// `hand bid` lines, with hands read by `parse_hand`.
+///
+/// Fields may be separated by any run of blanks, lines may end in CRLF and
+/// blank lines are skipped. Columns count characters from 1.
+fn parse_lines(
+    v: &str,
+    parse_hand: impl Fn(&str) -> Result<Hand, Error>,
+) -> Result<Vec<(Hand, u64)>, Error> {
+    let mut sets = Vec::new();
+    for (line, line_idx) in v.lines().zip(1..) {
+        let fields = fields(line);
+        let (hand, bid) = match fields[..] {
+            [] => continue,
+            [hand, bid] => (hand, bid),
+            [(col, _)] => {
+                return Err(Error::parse("missing bid")
+                    .at(line_idx, col)
+                    .with_text(line.trim()))
+            }
+            [_, _, (col, extra), ..] => {
+                return Err(Error::parse("unexpected text after the bid")
+                    .at(line_idx, col)
+                    .with_text(extra))
+            }
+        };
+        let parsed = parse_hand(hand.1).map_err(|e| {
+            match e.diagnostic().column {
+                Some(_) => e.offset_columns(hand.0 - 1),
+                None => e.at_column(hand.0),
+            }
+            .at_line(line_idx)
+        })?;
+        let bid = bid.1.parse::<u64>().map_err(|_| {
+            Error::parse("invalid bid")
+                .at(line_idx, bid.0)
+                .with_text(bid.1)
+        })?;
+        sets.push((parsed, bid));
+    }
+    Ok(sets)
+}
+
+/// Blank separated words of `line` and the columns they start at.
+fn fields(line: &str) -> Vec<(usize, &str)> {
+    let mut fields = Vec::new();
+    let mut start = None;
+    for ((idx, c), col) in line.char_indices().zip(1..) {
+        match (c.is_whitespace(), start) {
+            (false, None) => start = Some((col, idx)),
+            (true, Some((col, from))) => {
+                fields.push((col, &line[from..idx]));
+                start = None;
+            }
+            _ => (),
+        }
+    }
+    if let Some((col, from)) = start {
+        fields.push((col, &line[from..]));
+    }
+    fields
+}
+
+#[test]
+fn test_parse_inputs() {
+    let jacks = Ruleset::jacks();
+    let sets = parse_inputs("32T3K 765\nT55J5 684\n", &jacks).unwrap();
+    assert_eq!(
+        sets,
+        vec![
+            ("32T3K".parse().unwrap(), 765),
+            ("T55J5".parse().unwrap(), 684)
+        ]
+    );
+    let e = parse_inputs("32T3K 765\nT55J5 6x4", &jacks).unwrap_err();
+    assert_eq!(
+        (e.diagnostic().line, e.diagnostic().column),
+        (Some(2), Some(7))
+    );
+    let e = parse_inputs("32T3K 765\nT55Z5 684", &jacks).unwrap_err();
+    assert_eq!(
+        (e.diagnostic().line, e.diagnostic().column),
+        (Some(2), Some(4))
+    );
+    let messy = "  32T3K \t765\r\n\r\nT55J5 684  \r\n\n\n";
+    assert_eq!(parse_inputs(messy, &jacks), Ok(sets));
+    let position = |s: &str| {
+        let e = parse_inputs(s, &jacks).unwrap_err();
+        (e.diagnostic().line, e.diagnostic().column)
+    };
+    assert_eq!(position("32T3K 765\n  T55Z5 684"), (Some(2), Some(6)));
+    assert_eq!(position("32T3K 765\n\n  T55J 684"), (Some(3), Some(3)));
+    assert_eq!(position("32T3K 765\nT55J5"), (Some(2), Some(1)));
+    assert_eq!(position("32T3K 765 1"), (Some(1), Some(11)));
+    assert_eq!(position("32T3K -765"), (Some(1), Some(7)));
+    // columns count characters, not bytes
+    let rules = Ruleset::new("αβγ").unwrap().with_hand_len(2).unwrap();
+    let e = parse_inputs("αβ 1\nαβ x", &rules).unwrap_err();
+    assert_eq!(e.diagnostic().column, Some(4));
+    assert_eq!(parse_inputs("αβ 1", &rules).unwrap().len(), 1);
+}
+
+#[test]
+fn test_fields() {
+    assert_eq!(fields(" a  bc\td "), [(2, "a"), (5, "bc"), (8, "d")]);
+    assert!(fields("  ").is_empty());
+}
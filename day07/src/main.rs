@@ -1,144 +1,207 @@
-use std::{cmp::Ordering, collections::HashMap};
+//! Without arguments, print both answers. With any rule option, `--input`
+//! or `--explain`, print the winnings of the input under that one ruleset
+//! instead, or with `--explain` every ranked hand in a table, CSV or JSON.
+//!
+//! ```text
+//! day07 [--rules jacks|jokers] [--alphabet CARDS] [--wildcard CARD]
+//!       [--hand-len N] [--classifier groups|plain] [--input PATH|-]
+//!       [--explain table|csv|json]
+//! day07 --poker [--input PATH|-] [--explain table|csv|json]
+//! day07 --odds [--partial CARDS] [RULE OPTIONS]
+//! day07 --tournament [--players NAME,...] [--rounds N] [--chips N]
+//!       [--seed N] [RULE OPTIONS]
+//! ```
+//!
+//! `--alphabet` lists the cards weakest first, instead of picking
+//! `--rules`, which are `jacks` unless told otherwise. `--poker` reads suited
+//! hands such as `AsKd2c3h4h` and plays each one's best five cards.
+//! `--odds` prints how likely each hand type is, for any hand or for hands
+//! holding the `--partial` cards, without reading any input.
+//! `--tournament` plays rounds of dealt hands between `human`, `steady`,
+//! `bytype` and `random` players, `human` bidding on stdin.
+//!
+use std::{io, process::ExitCode};
 
-fn main() {
-    let v = std::fs::read_to_string("./day7.in").unwrap();
-    let mut hands = Vec::new();
-    let mut bids = Vec::new();
-    for line in v.split("\n") {
-        let l = line.split(" ").collect::<Vec<&str>>();
-        if l.len() < 2 {
-            continue;
+use aoc_core::{
+    args::Args,
+    input::{Resolver, Source},
+    rng::Rng,
+    Error, Solution,
+};
+use day07::{
+    odds::{self, Distribution},
+    parse_inputs, poker,
+    report::{self, Format},
+    tournament::{self, Player, Tournament},
+    winnings, Classifier, Day07, Hand, Ruleset,
+};
+
+fn main() -> ExitCode {
+    let mut args = Args::from_env();
+    let result = match args.flag("--help") {
+        true => Err(USAGE.to_string()),
+        false => command(args),
+    };
+    match result {
+        Ok(Output::Answers) => aoc_core::run::<Day07>(),
+        Ok(Output::Winnings(sets, None)) => println!("{}", winnings(&sets)),
+        Ok(Output::Winnings(sets, Some(format))) => {
+            print!("{}", report::render(&report::explain(sets), format))
+        }
+        Ok(Output::Odds(dist)) => print!("{}", dist),
+        Ok(Output::Done) => (),
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
         }
-        hands.push(String::from(l[0]));
-        bids.push(l[1].parse::<u64>().unwrap());
     }
-    println!("{}", calculate_bids(parse_inputs(hands, bids)));
+    ExitCode::SUCCESS
 }
 
-type Card = u32;
+const USAGE: &str = "usage: day07 [RULE OPTIONS] [--input PATH|-] [--explain table|csv|json]
+       day07 --poker [--input PATH|-] [--explain table|csv|json]
+       day07 --odds [--partial CARDS] [RULE OPTIONS]
+       day07 --tournament [--players NAME,...] [--rounds N] [--chips N] [--seed N]
+                          [RULE OPTIONS]
+players: human, steady, bytype, random
+rule options: [--rules jacks|jokers] [--alphabet CARDS] [--wildcard CARD]
+              [--hand-len N] [--classifier groups|plain]";
 
-fn parse_card(c: char) -> Card {
-    match c {
-        '2'..='9' => c.to_digit(10).unwrap(),
-        'T' => 10,
-        'J' => 11,
-        'Q' => 12,
-        'K' => 13,
-        'A' => 14,
-        _ => panic!("invalid card number"),
-    }
+enum Output {
+    Answers,
+    /// Hands and bids under the picked rules, and how to report them.
+    Winnings(Vec<(Hand, u64)>, Option<Format>),
+    Odds(Distribution),
+    /// Everything was printed along the way.
+    Done,
 }
 
-type CardSet = [Card; 5];
-
-fn parse_cardset(s: &str) -> CardSet {
-    if s.len() != 5 {
-        panic!("bad input");
+fn command(mut args: Args) -> Result<Output, String> {
+    if args.flag("--tournament") {
+        return play(args).map(|_| Output::Done);
     }
-    [
-        parse_card(s.chars().nth(0).unwrap()),
-        parse_card(s.chars().nth(1).unwrap()),
-        parse_card(s.chars().nth(2).unwrap()),
-        parse_card(s.chars().nth(3).unwrap()),
-        parse_card(s.chars().nth(4).unwrap()),
-    ]
-}
+    let poker = args.flag("--poker");
+    let odds = args.flag("--odds");
+    let partial = args.value("--partial")?;
+    let explain = args.parsed::<Format>("--explain")?;
+    let rules = rule_options(&mut args)?;
+    let source = args.parsed::<Source>("--input")?;
+    args.finish()?;
+    let in_day = |e: Error| e.in_day(Day07::DAY).to_string();
 
-#[test]
-fn test_parse_cardset() {
-    let ex = "32T3K";
-    println!("{:?}", parse_cardset(ex));
-}
-
-type CardType = u32;
-
-fn detect_card_type(cs: &CardSet) -> CardType {
-    let mut card_map = HashMap::new();
-    for card in cs {
-        match card_map.get_mut(&card) {
-            Some(card_num) => {
-                *card_num += 1;
-            }
-            None => {
-                card_map.insert(card, 1);
-            }
+    if odds {
+        if poker || explain.is_some() || source.is_some() {
+            return Err("--odds takes only rule options and --partial".to_string());
         }
+        let rules = rules.unwrap_or_else(Ruleset::jacks);
+        let dist = match partial {
+            Some(cards) => odds::complete(&rules, &cards),
+            None => odds::distribution(&rules),
+        };
+        return dist.map(Output::Odds).map_err(in_day);
     }
-    let mut v = card_map.values().cloned().collect::<Vec<i32>>();
-    v.sort();
-    v.reverse();
-    if v[0] == 1 {
-        return 1;
-    }
-    if v[0] == 2 && v[1] != 2 {
-        return 2;
-    }
-    if v[0] == 2 && v[1] == 2 {
-        return 3;
-    }
-    if v[0] == 3 && v[1] == 1 {
-        return 4;
+    if partial.is_some() {
+        return Err("--partial needs --odds".to_string());
     }
-    if v[0] == 3 && v[1] == 2 {
-        return 5;
-    }
-    if v[0] == 4 {
-        return 6;
-    }
-    7
+    let source = match (poker, &rules, source) {
+        (true, Some(_), _) => return Err("--poker takes no rule options".to_string()),
+        (false, None, None) if explain.is_none() => return Ok(Output::Answers),
+        (_, _, source) => source.unwrap_or(Source::Cache),
+    };
+    let input = Resolver::from_env()
+        .resolve(Day07::DAY, &source)
+        .map_err(in_day)?;
+    let sets = match poker {
+        true => poker::parse_inputs(&input),
+        false => parse_inputs(&input, &rules.unwrap_or_else(Ruleset::jacks)),
+    };
+    sets.map(|sets| Output::Winnings(sets, explain))
+        .map_err(in_day)
 }
 
-#[test]
-fn test_detect() {
-    let strs = ["32T3K", "T55J5", "KK677", "KTJJT", "QQQJA"];
+fn play(mut args: Args) -> Result<(), String> {
+    let names = args
+        .value("--players")?
+        .unwrap_or("human,steady,bytype,random".to_string());
+    let rounds = args.parsed::<usize>("--rounds")?.unwrap_or(10);
+    let chips = args.parsed::<u64>("--chips")?.unwrap_or(1000);
+    let seed = args.parsed::<u64>("--seed")?.unwrap_or(2023);
+    let rules = rule_options(&mut args)?.unwrap_or_else(Ruleset::jacks);
+    args.finish()?;
 
-    for s in strs {
-        println!("{}", detect_card_type(&parse_cardset(s)));
+    // every human reads stdin, whose lock would wait on the first one
+    if names
+        .split(',')
+        .filter(|name| name.trim() == "human")
+        .count()
+        > 1
+    {
+        return Err("only one human player can bid on stdin".to_string());
     }
-}
-
-fn compare_card_set(cs1: &CardSet, cs2: &CardSet) -> Ordering {
-    if detect_card_type(cs1) > detect_card_type(cs2) {
-        return Ordering::Greater;
-    } else if detect_card_type(cs2) > detect_card_type(cs1) {
-        return Ordering::Less;
+    let mut game = Tournament::new(rules, seed, rounds, chips);
+    for (name, idx) in names.split(',').zip(1..) {
+        let player: Box<dyn Player> = match name.trim() {
+            "human" => Box::new(tournament::Human::new(
+                "you",
+                io::stdin().lock(),
+                io::stdout(),
+            )),
+            "steady" => Box::new(tournament::Steady),
+            "bytype" => Box::new(tournament::ByType),
+            "random" => Box::new(tournament::Random(Rng::new(seed.wrapping_add(idx)))),
+            other => return Err(format!("unknown player {:?}", other)),
+        };
+        game = game.with_player(player);
     }
-    let mut result = Ordering::Equal;
-    for (c1, c2) in cs1.iter().zip(cs2) {
-        match c1.cmp(&c2) {
-            o @ Ordering::Less | o @ Ordering::Greater => {
-                result = o;
-                break;
-            }
-            _ => continue,
-        }
+    game.play(|round| print!("{}", round))
+        .map_err(|e| e.in_day(Day07::DAY).to_string())?;
+    println!("standings");
+    for (name, score) in game.standings() {
+        println!("  {:<10} {:>8}", name, score);
     }
-    result
+    Ok(())
 }
 
-fn calculate_bids(mut sets: Vec<(CardSet, u64)>) -> u64 {
-    sets.sort_by(|a, b| compare_card_set(&a.0, &b.0));
-    sets.iter()
-        .map(|a| a.1)
-        .zip(1..)
-        .fold(0, |acc, (rank, bid)| acc + rank * bid)
-}
-
-#[test]
-fn test_calculate_bids() {
-    let strs = ["32T3K", "T55J5", "KK677", "KTJJT", "QQQJA"];
-    let bids = [765, 684, 28, 220, 483];
-    let mut v = Vec::new();
-    for (s, i) in strs.into_iter().zip(bids) {
-        v.push((parse_cardset(s), i))
+/// The ruleset picked on the command line, `None` when nothing was picked.
+fn rule_options(args: &mut Args) -> Result<Option<Ruleset>, String> {
+    let preset = args.value("--rules")?;
+    let alphabet = args.value("--alphabet")?;
+    let wildcard = args.parsed::<char>("--wildcard")?;
+    let hand_len = args.parsed::<usize>("--hand-len")?;
+    let classifier = args.parsed::<Classifier>("--classifier")?;
+    if (&preset, &alphabet, wildcard, hand_len, classifier) == (&None, &None, None, None, None) {
+        return Ok(None);
     }
-    println!("{}", calculate_bids(v));
+    let rules = ruleset(preset.as_deref(), alphabet.as_deref(), wildcard, hand_len)
+        .map_err(|e| e.to_string())?;
+    Ok(Some(match classifier {
+        Some(classifier) => rules.with_classifier(classifier),
+        None => rules,
+    }))
 }
 
-fn parse_inputs(strs: Vec<String>, bids: Vec<u64>) -> Vec<(CardSet, u64)> {
-    let mut v = Vec::new();
-    for (s, i) in strs.into_iter().zip(bids) {
-        v.push((parse_cardset(&s), i))
+fn ruleset(
+    preset: Option<&str>,
+    alphabet: Option<&str>,
+    wildcard: Option<char>,
+    hand_len: Option<usize>,
+) -> Result<Ruleset, Error> {
+    let mut rules = match (preset, alphabet) {
+        (Some(_), Some(_)) => {
+            return Err(Error::parse("--rules and --alphabet cannot be combined"))
+        }
+        (None, Some(alphabet)) => Ruleset::new(alphabet)?,
+        (None | Some("jacks"), None) => Ruleset::jacks(),
+        (Some("jokers"), None) => Ruleset::jokers(),
+        (Some(other), None) => {
+            return Err(Error::parse("rules must be jacks or jokers").with_text(other))
+        }
+    };
+    if let Some(c) = wildcard {
+        rules = rules.with_wildcard(c)?;
+    }
+    if let Some(n) = hand_len {
+        rules = rules.with_hand_len(n)?;
     }
-    v
+    Ok(rules)
 }
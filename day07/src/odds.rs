@@ -0,0 +1,189 @@
+//! Exact odds of each [`HandType`], found by dealing every possible hand.
+//!
+use std::{collections::BTreeMap, fmt};
+
+use aoc_core::Error;
+
+use crate::{Card, HandType, Ruleset};
+
+/// Hands we are willing to enumerate for one answer.
+const MAX_DEALS: u64 = 100_000_000;
+
+/// How many of `total` equally likely hands end up as each type.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Distribution {
+    counts: BTreeMap<HandType, u64>,
+    total: u64,
+}
+
+impl Distribution {
+    pub fn count(&self, hand_type: HandType) -> u64 {
+        self.counts.get(&hand_type).copied().unwrap_or(0)
+    }
+
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    pub fn probability(&self, hand_type: HandType) -> f64 {
+        self.count(hand_type) as f64 / self.total as f64
+    }
+}
+
+/// Weakest type first, only the types that can happen.
+impl fmt::Display for Distribution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (&hand_type, &count) in &self.counts {
+            writeln!(
+                f,
+                "{:<15}  {:>10} / {}  {:>9.5}%",
+                hand_type,
+                count,
+                self.total,
+                self.probability(hand_type) * 100.0
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Types of every hand the alphabet can spell.
+pub fn distribution(rules: &Ruleset) -> Result<Distribution, Error> {
+    deal(rules, &[])
+}
+
+/// Types of every way to fill up a hand that starts with `partial`. Only
+/// which cards are known matters, not where they sit.
+pub fn complete(rules: &Ruleset, partial: &str) -> Result<Distribution, Error> {
+    let known = partial
+        .chars()
+        .zip(1..)
+        .map(|(c, col)| rules.card(c).map_err(|e| e.at_column(col)))
+        .collect::<Result<Vec<_>, Error>>()?;
+    if known.len() > rules.hand_len() {
+        return Err(
+            Error::parse(format!("a hand has at most {} cards", rules.hand_len()))
+                .with_text(partial),
+        );
+    }
+    deal(rules, &known)
+}
+
+fn deal(rules: &Ruleset, known: &[Card]) -> Result<Distribution, Error> {
+    let free = rules.hand_len() - known.len();
+    let size = rules.alphabet_len() as u64;
+    let total = (0..free)
+        .try_fold(1_u64, |n, _| n.checked_mul(size))
+        .filter(|&n| n <= MAX_DEALS)
+        .ok_or_else(|| Error::solve(format!("more than {} hands to deal", MAX_DEALS)))?;
+
+    let mut cards = known.to_vec();
+    cards.resize(rules.hand_len(), 0);
+    let mut dist = Distribution {
+        total,
+        ..Distribution::default()
+    };
+    // count through the free cards like an odometer
+    loop {
+        *dist.counts.entry(rules.classify(&cards)).or_insert(0) += 1;
+        let mut idx = known.len();
+        loop {
+            if idx == cards.len() {
+                return Ok(dist);
+            }
+            cards[idx] += 1;
+            if cards[idx] < size as Card {
+                break;
+            }
+            cards[idx] = 0;
+            idx += 1;
+        }
+    }
+}
+
+#[test]
+fn test_distribution() {
+    use HandType::*;
+    let dist = distribution(&Ruleset::jacks()).unwrap();
+    assert_eq!(dist.total(), 13_u64.pow(5));
+    let expected = [
+        (HighCard, 154440),
+        (OnePair, 171600),
+        (TwoPair, 25740),
+        (ThreeOfAKind, 17160),
+        (FullHouse, 1560),
+        (FourOfAKind, 780),
+        (FiveOfAKind, 13),
+    ];
+    for (t, n) in expected {
+        assert_eq!(dist.count(t), n, "{}", t);
+    }
+    assert_eq!(dist.count(Straight), 0);
+    assert!((dist.probability(FiveOfAKind) - 13.0 / 371293.0).abs() < 1e-15);
+    assert!(dist
+        .to_string()
+        .starts_with("high card            154440 / 371293   41.59518%"));
+
+    let jokers = distribution(&Ruleset::jokers()).unwrap();
+    assert_eq!(jokers.total(), dist.total());
+    // jokers only ever move hands up
+    assert!(jokers.count(HighCard) < dist.count(HighCard));
+    assert!(jokers.count(FiveOfAKind) > dist.count(FiveOfAKind));
+}
+
+/// Every hand under `rules`, compared with the best type its wildcards
+/// could reach by standing in for any cards at all, one by one.
+#[test]
+fn test_wildcards_exhaustively() {
+    let jokers = Ruleset::jokers();
+    let plain = Ruleset::new("J23456789TQKA").unwrap();
+    let best = |cards: &mut Vec<Card>| {
+        fn go(plain: &Ruleset, cards: &mut Vec<Card>, from: usize) -> HandType {
+            match cards[from..].iter().position(|&c| c == 0) {
+                None => plain.classify(cards),
+                Some(at) => {
+                    let at = from + at;
+                    let best = (1..13)
+                        .map(|c| {
+                            cards[at] = c;
+                            go(plain, cards, at + 1)
+                        })
+                        .max()
+                        .unwrap();
+                    cards[at] = 0;
+                    best
+                }
+            }
+        }
+        go(&plain, cards, 0)
+    };
+    let mut cards = vec![0; 5];
+    for n in 0..13_u32.pow(5) {
+        for (idx, card) in cards.iter_mut().enumerate() {
+            *card = n / 13_u32.pow(idx as u32) % 13;
+        }
+        let t = jokers.classify(&cards);
+        assert_eq!(t, best(&mut cards), "{:?}", cards);
+    }
+}
+
+#[test]
+fn test_complete() {
+    use HandType::*;
+    let jacks = Ruleset::jacks();
+    let dist = complete(&jacks, "KKKK").unwrap();
+    assert_eq!(dist.total(), 13);
+    assert_eq!((dist.count(FourOfAKind), dist.count(FiveOfAKind)), (12, 1));
+    let dist = complete(&Ruleset::jokers(), "KKJ").unwrap();
+    assert_eq!(dist.total(), 169);
+    assert_eq!((dist.count(OnePair), dist.count(ThreeOfAKind)), (0, 110));
+    assert_eq!(complete(&jacks, "23456").unwrap().count(HighCard), 1);
+    assert_eq!(complete(&jacks, "").unwrap(), distribution(&jacks).unwrap());
+    assert!(complete(&jacks, "234567").is_err());
+    assert_eq!(
+        complete(&jacks, "2X").unwrap_err().diagnostic().column,
+        Some(2)
+    );
+    let long = Ruleset::jacks().with_hand_len(15).unwrap();
+    assert!(distribution(&long).is_err());
+}
@@ -0,0 +1,245 @@
+//! Standard poker over suited cards such as `AsKdQhJcTs`, ranked with the
+//! same [`Hand`] ordering as Camel Cards.
+//!
+//! Tie breaks follow poker rather than Camel Cards: a hand's cards are
+//! stored larger groups first, then higher ranks first, and the ace of a
+//! wheel (`5432A`) counts below the `2`. There are no wildcards.
+//!
+use std::fmt;
+
+use aoc_core::Error;
+
+use crate::{pack_key, parse_lines, Card, Hand, HandType, Ruleset};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Suit {
+    Clubs,
+    Diamonds,
+    Hearts,
+    Spades,
+}
+
+impl Suit {
+    fn parse(c: char) -> Result<Suit, Error> {
+        match c {
+            'c' => Ok(Suit::Clubs),
+            'd' => Ok(Suit::Diamonds),
+            'h' => Ok(Suit::Hearts),
+            's' => Ok(Suit::Spades),
+            _ => Err(Error::parse("invalid suit").with_text(c)),
+        }
+    }
+
+    fn symbol(self) -> char {
+        match self {
+            Suit::Clubs => 'c',
+            Suit::Diamonds => 'd',
+            Suit::Hearts => 'h',
+            Suit::Spades => 's',
+        }
+    }
+}
+
+/// A rank from `2` to `A` and a suit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PokerCard {
+    face: char,
+    suit: Suit,
+    /// `1` for a `2` up to `13` for an ace, leaving `0` for a low ace.
+    rank: Card,
+}
+
+impl fmt::Display for PokerCard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.face, self.suit.symbol())
+    }
+}
+
+const LOW_ACE: Card = 0;
+const ACE: Card = 13;
+
+/// Cards written rank then suit with nothing in between, errors point at
+/// the offending character by column.
+pub fn parse_cards(s: &str) -> Result<Vec<PokerCard>, Error> {
+    let ranks = Ruleset::jacks();
+    let chars = s.chars().collect::<Vec<_>>();
+    let mut cards: Vec<PokerCard> = Vec::new();
+    for (pair, idx) in chars.chunks(2).zip((1..).step_by(2)) {
+        let (face, suit) = match pair {
+            &[face, suit] => (face, suit),
+            _ => {
+                return Err(Error::parse("card without a suit")
+                    .at_column(idx)
+                    .with_text(s))
+            }
+        };
+        let card = PokerCard {
+            face,
+            rank: ranks.card(face).map_err(|e| e.at_column(idx))? + 1,
+            suit: Suit::parse(suit).map_err(|e| e.at_column(idx + 1))?,
+        };
+        if cards.contains(&card) {
+            return Err(Error::parse("card dealt twice")
+                .at_column(idx)
+                .with_text(card.to_string()));
+        }
+        cards.push(card);
+    }
+    Ok(cards)
+}
+
+/// The type and tie break order of exactly five cards.
+fn evaluate(cards: &[PokerCard]) -> Hand {
+    let mut counts = [0_usize; ACE as usize + 1];
+    for card in cards {
+        counts[card.rank as usize] += 1;
+    }
+    let mut groups = (1..=ACE)
+        .rev()
+        .filter(|&r| counts[r as usize] > 0)
+        .map(|r| (counts[r as usize], r))
+        .collect::<Vec<_>>();
+    // stable, so equal groups stay highest rank first
+    groups.sort_by_key(|g| std::cmp::Reverse(g.0));
+    let mut order = groups
+        .iter()
+        .flat_map(|&(n, r)| std::iter::repeat_n(r, n))
+        .collect::<Vec<_>>();
+
+    let flush = cards.iter().all(|c| c.suit == cards[0].suit);
+    let straight = groups.len() == 5
+        && match order[0] - order[4] {
+            4 => true,
+            12 if order[1] == 4 => {
+                order.rotate_left(1);
+                order[4] = LOW_ACE;
+                true
+            }
+            _ => false,
+        };
+    let hand_type = match (groups[0].0, groups.get(1).map_or(0, |g| g.0)) {
+        _ if straight && flush => HandType::StraightFlush,
+        (4, _) => HandType::FourOfAKind,
+        (3, 2) => HandType::FullHouse,
+        _ if flush => HandType::Flush,
+        _ if straight => HandType::Straight,
+        (3, _) => HandType::ThreeOfAKind,
+        (2, 2) => HandType::TwoPair,
+        (2, _) => HandType::OnePair,
+        _ => HandType::HighCard,
+    };
+    Hand {
+        faces: cards.iter().map(|c| c.to_string()).collect(),
+        key: pack_key(hand_type, &order, 4),
+        cards: order,
+        hand_type,
+        wildcard_as: None,
+    }
+}
+
+/// The strongest five card hand among `cards`, so seven cards play like
+/// Texas hold'em.
+pub fn best_hand(cards: &[PokerCard]) -> Result<Hand, Error> {
+    if cards.len() < 5 {
+        return Err(Error::parse("a poker hand needs at least 5 cards"));
+    }
+    let mut best: Option<Hand> = None;
+    let mut pick = Vec::with_capacity(5);
+    for_each_five(cards, 0, &mut pick, &mut |five| {
+        let hand = evaluate(five);
+        if best.as_ref().is_none_or(|b| hand > *b) {
+            best = Some(hand);
+        }
+    });
+    Ok(best.unwrap())
+}
+
+fn for_each_five(
+    cards: &[PokerCard],
+    from: usize,
+    pick: &mut Vec<PokerCard>,
+    f: &mut impl FnMut(&[PokerCard]),
+) {
+    if pick.len() == 5 {
+        return f(pick);
+    }
+    for idx in from..=cards.len() - (5 - pick.len()) {
+        pick.push(cards[idx]);
+        for_each_five(cards, idx + 1, pick, f);
+        pick.pop();
+    }
+}
+
+/// Read one hand of five or more suited cards and keep its best five.
+pub fn hand(s: &str) -> Result<Hand, Error> {
+    best_hand(&parse_cards(s)?).map_err(|e| e.with_text(s))
+}
+
+/// `cards bid` lines, each hand playing its best five cards.
+pub fn parse_inputs(v: &str) -> Result<Vec<(Hand, u64)>, Error> {
+    parse_lines(v, hand)
+}
+
+#[test]
+fn test_parse_cards() {
+    let cards = parse_cards("AsTd2h").unwrap();
+    assert_eq!(cards.len(), 3);
+    assert_eq!((cards[0].rank, cards[0].suit), (ACE, Suit::Spades));
+    assert_eq!(cards[1].to_string(), "Td");
+    let e = parse_cards("AsTx").unwrap_err();
+    assert_eq!(e.diagnostic().column, Some(4));
+    let e = parse_cards("As1s").unwrap_err();
+    assert_eq!(e.diagnostic().column, Some(3));
+    assert!(parse_cards("AsT").is_err());
+    assert!(parse_cards("AsAs").is_err());
+}
+
+#[test]
+fn test_evaluate() {
+    use HandType::*;
+    let strs = [
+        ("AsKsQsJsTs", StraightFlush),
+        ("9c9d9h9s2c", FourOfAKind),
+        ("3c3d3h2s2c", FullHouse),
+        ("2h7h9hJhKh", Flush),
+        ("9c8dTh7sJc", Straight),
+        ("Ac2d3h4s5c", Straight),
+        ("3c3d3hKs2c", ThreeOfAKind),
+        ("3c3dKhKs2c", TwoPair),
+        ("3c3dAhKs2c", OnePair),
+        ("3c5dAhKs2c", HighCard),
+        ("AcKdQhJs9c", HighCard),
+    ];
+    for (s, t) in strs {
+        assert_eq!(hand(s).unwrap().hand_type(), t, "{}", s);
+    }
+}
+
+#[test]
+fn test_tie_breaks() {
+    let hand = |s: &str| hand(s).unwrap();
+    // the wheel is the lowest straight
+    assert!(hand("Ac2d3h4s5c") < hand("2c3d4h5s6c"));
+    assert!(hand("Ac2d3h4s5c") > hand("3c3d3hKs2c"));
+    // pairs before kickers, whatever the position
+    assert!(hand("2c2dAhKsQc") < hand("3c4d3hKs2c"));
+    assert!(hand("KcKd3h4s5c") < hand("KhKs3c4d6c"));
+    assert_eq!(
+        hand("KcKd3h4s5c").cmp(&hand("KhKs5h4c3d")),
+        std::cmp::Ordering::Equal
+    );
+    assert_eq!(hand("KcKd3h4s5c"), hand("KhKs5h4c3d"));
+    assert!(hand("2h7h9hJhKh") > hand("AcKdQhJsTc"));
+}
+
+#[test]
+fn test_best_of_seven() {
+    let best = hand("AhKh2c3d4s5hQh").unwrap();
+    assert_eq!(best.hand_type(), HandType::Straight);
+    assert_eq!(best.to_string(), "Ah2c3d4s5h");
+    let best = hand("AhKhQhJhTh9h8h").unwrap();
+    assert_eq!(best.to_string(), "AhKhQhJhTh");
+    assert!(hand("AhKh").is_err());
+    let sets = parse_inputs("AsKsQsJsTs 3\n2c2d7h8s9c 5\n").unwrap();
+    assert_eq!(crate::winnings(&sets), 5 + 3 * 2);
+}
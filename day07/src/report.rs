@@ -0,0 +1,184 @@
+//! Every hand of a game with its rank and share of the winnings, to compare
+//! rankings between rule variants.
+//!
+use std::{fmt::Write, str::FromStr};
+
+use aoc_core::json;
+
+use crate::Hand;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Table,
+    Csv,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(Format::Table),
+            "csv" => Ok(Format::Csv),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("format must be table, csv or json, got {:?}", s)),
+        }
+    }
+}
+
+/// One ranked hand.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    pub rank: u64,
+    pub hand: Hand,
+    pub bid: u64,
+    /// Rank times bid.
+    pub winnings: u64,
+}
+
+/// Rank hands as they are, weakest first, the same way
+/// [`winnings`](crate::winnings) does.
+pub fn explain(mut sets: Vec<(Hand, u64)>) -> Vec<Row> {
+    sets.sort_by(|a, b| a.0.cmp(&b.0));
+    sets.into_iter()
+        .zip(1..)
+        .map(|((hand, bid), rank)| Row {
+            rank,
+            hand,
+            bid,
+            winnings: rank * bid,
+        })
+        .collect()
+}
+
+pub fn render(rows: &[Row], format: Format) -> String {
+    match format {
+        Format::Table => table(rows),
+        Format::Csv => csv(rows),
+        Format::Json => json(rows),
+    }
+}
+
+fn wild(row: &Row) -> String {
+    row.hand.wildcard_as().map_or(String::new(), String::from)
+}
+
+fn table(rows: &[Row]) -> String {
+    let width = rows
+        .iter()
+        .map(|r| r.hand.to_string().chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+    let mut out = format!(
+        "{:>5}  {:<width$}  {:<15}  {:<4}  {:>6}  {:>10}\n",
+        "rank", "hand", "type", "wild", "bid", "winnings"
+    );
+    for row in rows {
+        let _ = writeln!(
+            out,
+            "{:>5}  {:<width$}  {:<15}  {:<4}  {:>6}  {:>10}",
+            row.rank,
+            row.hand,
+            row.hand.hand_type(),
+            wild(row),
+            row.bid,
+            row.winnings
+        );
+    }
+    let total = rows.iter().map(|r| r.winnings).sum::<u64>();
+    let _ = writeln!(out, "{:>5}  {:>w$}", "total", total, w = width + 43);
+    out
+}
+
+fn csv(rows: &[Row]) -> String {
+    let mut out = "rank,hand,type,wild,bid,winnings\n".to_string();
+    for row in rows {
+        let _ = writeln!(
+            out,
+            "{},{},{},{},{},{}",
+            row.rank,
+            csv_field(&row.hand.to_string()),
+            row.hand.hand_type(),
+            csv_field(&wild(row)),
+            row.bid,
+            row.winnings
+        );
+    }
+    out
+}
+
+fn csv_field(s: &str) -> String {
+    match s.contains([',', '"', '\n']) {
+        true => format!("\"{}\"", s.replace('"', "\"\"")),
+        false => s.to_string(),
+    }
+}
+
+fn json(rows: &[Row]) -> String {
+    let mut out = "[\n".to_string();
+    for (idx, row) in rows.iter().enumerate() {
+        let wild = match row.hand.wildcard_as() {
+            Some(c) => json::string(&c.to_string()),
+            None => "null".to_string(),
+        };
+        let _ = write!(
+            out,
+            "  {{\"rank\": {}, \"hand\": {}, \"type\": {}, \"wild\": {}, \"bid\": {}, \"winnings\": {}}}",
+            row.rank,
+            json::string(&row.hand.to_string()),
+            json::string(&row.hand.hand_type().to_string()),
+            wild,
+            row.bid,
+            row.winnings
+        );
+        out.push_str(if idx + 1 < rows.len() { ",\n" } else { "\n" });
+    }
+    out.push_str("]\n");
+    out
+}
+
+#[cfg(test)]
+fn example() -> Vec<Row> {
+    let jokers = crate::Ruleset::jokers();
+    let sets = crate::parse_inputs("32T3K 765\nT55J5 684\nKK677 28\n", &jokers).unwrap();
+    explain(sets)
+}
+
+#[test]
+fn test_explain() {
+    let rows = example();
+    let ranked = rows
+        .iter()
+        .map(|r| (r.rank, r.hand.to_string(), r.winnings))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        ranked,
+        [
+            (1, "32T3K".to_string(), 765),
+            (2, "KK677".to_string(), 56),
+            (3, "T55J5".to_string(), 2052)
+        ]
+    );
+    assert_eq!(rows[2].hand.wildcard_as(), Some('5'));
+}
+
+#[test]
+fn test_render() {
+    let rows = example();
+    let table = render(&rows, Format::Table);
+    assert_eq!(
+        table.lines().nth(3),
+        Some("    3  T55J5  four of a kind   5        684        2052")
+    );
+    assert!(table.trim_end().ends_with("2873"));
+    let csv = render(&rows, Format::Csv);
+    assert_eq!(csv.lines().nth(1), Some("1,32T3K,one pair,,765,765"));
+    let json = render(&rows, Format::Json);
+    assert!(json.contains(
+        r#"{"rank": 3, "hand": "T55J5", "type": "four of a kind", "wild": "5", "bid": 684, "winnings": 2052}"#
+    ));
+    assert!(json.contains(r#""wild": null"#));
+    assert_eq!(csv_field("a,b"), "\"a,b\"");
+}
@@ -0,0 +1,285 @@
+//! Which cards exist, how they rank, and how a hand gets its type.
+//!
+use std::{fmt, str::FromStr};
+
+use aoc_core::{rng::Rng, Error};
+
+use crate::{pack_key, Card, Hand, HandType};
+
+/// Cards an alphabet may hold, so a hand's histogram fits on the stack.
+const MAX_ALPHABET: usize = 64;
+
+/// Bits of a packed key left for the cards, below the hand type.
+const KEY_CARD_BITS: u32 = 60;
+
+/// How a hand is sorted into a [`HandType`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Classifier {
+    /// By the sizes of its groups of equal cards, wildcards joining the
+    /// largest group.
+    Groups,
+    /// Every hand is a [`HandType::HighCard`], so only the cards themselves
+    /// decide the order.
+    Plain,
+}
+
+impl FromStr for Classifier {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "groups" => Ok(Classifier::Groups),
+            "plain" => Ok(Classifier::Plain),
+            _ => Err(format!("classifier must be groups or plain, got {:?}", s)),
+        }
+    }
+}
+
+/// Card alphabet, wildcard, hand length and classifier of one game.
+///
+/// A card's strength is its position in the alphabet, weakest first, so a
+/// wildcard placed first loses every tie break. Alphabets hold at most 64
+/// cards, and hands are short enough to pack into a 64 bit sort key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ruleset {
+    alphabet: Vec<char>,
+    wildcard: Option<Card>,
+    hand_len: usize,
+    classifier: Classifier,
+    /// Bits one card takes in a packed key.
+    card_bits: u32,
+}
+
+impl Ruleset {
+    /// Five cards from `alphabet`, no wildcard, classified by groups.
+    pub fn new(alphabet: &str) -> Result<Ruleset, Error> {
+        let alphabet = alphabet.chars().collect::<Vec<_>>();
+        if alphabet.is_empty() {
+            return Err(Error::parse("the card alphabet is empty"));
+        }
+        if alphabet.len() > MAX_ALPHABET {
+            return Err(Error::parse(format!(
+                "the card alphabet has more than {} cards",
+                MAX_ALPHABET
+            )));
+        }
+        for (idx, c) in alphabet.iter().enumerate() {
+            if alphabet[..idx].contains(c) {
+                return Err(Error::parse("card appears twice in the alphabet")
+                    .at_column(idx + 1)
+                    .with_text(*c));
+            }
+        }
+        let card_bits = (usize::BITS - (alphabet.len() - 1).leading_zeros()).max(1);
+        Ruleset {
+            alphabet,
+            wildcard: None,
+            hand_len: 1,
+            classifier: Classifier::Groups,
+            card_bits,
+        }
+        .with_hand_len(5)
+    }
+
+    /// Part one: `J` is a jack, between `T` and `Q`.
+    pub fn jacks() -> Ruleset {
+        Ruleset::new("23456789TJQKA").unwrap()
+    }
+
+    /// Part two: `J` is a joker, the weakest card on its own, but it joins
+    /// whichever other card makes the strongest hand.
+    pub fn jokers() -> Ruleset {
+        Ruleset::new("J23456789TQKA")
+            .and_then(|r| r.with_wildcard('J'))
+            .unwrap()
+    }
+
+    pub fn with_wildcard(mut self, c: char) -> Result<Ruleset, Error> {
+        self.wildcard = Some(self.card(c)?);
+        Ok(self)
+    }
+
+    pub fn with_hand_len(mut self, hand_len: usize) -> Result<Ruleset, Error> {
+        if hand_len == 0 {
+            return Err(Error::parse("a hand needs at least one card"));
+        }
+        if hand_len as u32 * self.card_bits > KEY_CARD_BITS {
+            return Err(Error::parse(format!(
+                "hands of this alphabet hold at most {} cards",
+                KEY_CARD_BITS / self.card_bits
+            )));
+        }
+        self.hand_len = hand_len;
+        Ok(self)
+    }
+
+    pub fn with_classifier(mut self, classifier: Classifier) -> Ruleset {
+        self.classifier = classifier;
+        self
+    }
+
+    pub fn hand_len(&self) -> usize {
+        self.hand_len
+    }
+
+    pub fn alphabet_len(&self) -> usize {
+        self.alphabet.len()
+    }
+
+    pub(crate) fn card(&self, c: char) -> Result<Card, Error> {
+        match self.alphabet.iter().position(|&a| a == c) {
+            Some(idx) => Ok(idx as Card),
+            None => Err(Error::parse("invalid card").with_text(c)),
+        }
+    }
+
+    pub(crate) fn symbol(&self, card: Card) -> char {
+        self.alphabet[card as usize]
+    }
+
+    /// Errors point at the offending card by column.
+    pub(crate) fn cards(&self, s: &str) -> Result<Vec<Card>, Error> {
+        if s.chars().count() != self.hand_len {
+            return Err(
+                Error::parse(format!("a hand has exactly {} cards", self.hand_len)).with_text(s),
+            );
+        }
+        s.chars()
+            .zip(1..)
+            .map(|(c, col)| self.card(c).map_err(|e| e.at_column(col)))
+            .collect()
+    }
+
+    /// Type in the top bits and cards below, so keys order like hands.
+    pub(crate) fn key(&self, hand_type: HandType, cards: &[Card]) -> u64 {
+        pack_key(hand_type, cards, self.card_bits)
+    }
+
+    /// A hand of cards drawn uniformly, with repeats.
+    pub fn random_hand(&self, rng: &mut Rng) -> Hand {
+        let s = (0..self.hand_len)
+            .map(|_| *rng.pick(&self.alphabet))
+            .collect::<String>();
+        Hand::parse(&s, self).unwrap()
+    }
+
+    fn histogram(&self, cards: &[Card]) -> [u8; MAX_ALPHABET] {
+        let mut counts = [0; MAX_ALPHABET];
+        for &card in cards {
+            counts[card as usize] += 1;
+        }
+        counts
+    }
+
+    pub(crate) fn classify(&self, cards: &[Card]) -> HandType {
+        match self.classifier {
+            Classifier::Groups => self.classify_groups(cards),
+            Classifier::Plain => HandType::HighCard,
+        }
+    }
+
+    /// Hands longer than five cards land on the rung their two largest
+    /// groups reach, so six of a kind is still a [`HandType::FiveOfAKind`].
+    fn classify_groups(&self, cards: &[Card]) -> HandType {
+        let mut counts = self.histogram(cards);
+        // wildcards always do best joining the largest group
+        let wild = match self.wildcard {
+            Some(w) => std::mem::take(&mut counts[w as usize]),
+            None => 0,
+        };
+        let (mut first, mut second) = (0, 0);
+        for &n in &counts[..self.alphabet.len()] {
+            if n > first {
+                (first, second) = (n, first);
+            } else if n > second {
+                second = n;
+            }
+        }
+        match (first + wild, second) {
+            (1, _) => HandType::HighCard,
+            (2, 2) => HandType::TwoPair,
+            (2, _) => HandType::OnePair,
+            (3, 2..) => HandType::FullHouse,
+            (3, _) => HandType::ThreeOfAKind,
+            (4, _) => HandType::FourOfAKind,
+            _ => HandType::FiveOfAKind,
+        }
+    }
+
+    /// What the wildcards of a hand turn into to reach its type: the card of
+    /// the largest group, the strongest one on ties, or the strongest card
+    /// of all when every card is wild.
+    pub(crate) fn wildcard_as(&self, cards: &[Card]) -> Option<char> {
+        let w = self.wildcard?;
+        if self.classifier == Classifier::Plain || !cards.contains(&w) {
+            return None;
+        }
+        let mut counts = self.histogram(cards);
+        counts[w as usize] = 0;
+        let target = (0..self.alphabet.len())
+            .max_by_key(|&card| (counts[card], card))
+            .unwrap();
+        Some(self.alphabet[target])
+    }
+}
+
+impl fmt::Display for Ruleset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let alphabet = self.alphabet.iter().collect::<String>();
+        write!(f, "{} cards of {}", self.hand_len, alphabet)?;
+        if let Some(w) = self.wildcard {
+            write!(f, ", {} wild", self.symbol(w))?;
+        }
+        if self.classifier == Classifier::Plain {
+            write!(f, ", no hand types")?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_ruleset() {
+    assert!(Ruleset::new("").is_err());
+    let e = Ruleset::new("ABCA").unwrap_err();
+    assert_eq!(e.diagnostic().column, Some(4));
+    assert!(Ruleset::jacks().with_wildcard('X').is_err());
+    assert!(Ruleset::jacks().with_hand_len(0).is_err());
+    assert!(Ruleset::jacks().with_hand_len(15).is_ok());
+    assert!(Ruleset::jacks().with_hand_len(16).is_err());
+    assert!(Ruleset::new(&"x".repeat(65)).is_err());
+    assert_eq!(Ruleset::new("ab").unwrap().card_bits, 1);
+    assert_eq!(Ruleset::jacks().card_bits, 4);
+    assert_eq!(
+        Ruleset::jokers().to_string(),
+        "5 cards of J23456789TQKA, J wild"
+    );
+}
+
+#[test]
+fn test_classify_custom() {
+    use HandType::*;
+    let rules = Ruleset::new("abc").unwrap().with_hand_len(3).unwrap();
+    let classify = |r: &Ruleset, s: &str| r.classify(&r.cards(s).unwrap());
+    assert_eq!(classify(&rules, "abc"), HighCard);
+    assert_eq!(classify(&rules, "aba"), OnePair);
+    assert_eq!(classify(&rules, "ccc"), ThreeOfAKind);
+    let rules = rules.with_wildcard('c').unwrap();
+    assert_eq!(classify(&rules, "acc"), ThreeOfAKind);
+    let rules = Ruleset::jacks().with_hand_len(7).unwrap();
+    assert_eq!(classify(&rules, "AAAKKK2"), FullHouse);
+    assert_eq!(classify(&rules, "AAAAAA2"), FiveOfAKind);
+    let rules = Ruleset::jacks().with_classifier(Classifier::Plain);
+    assert_eq!(classify(&rules, "AAAAA"), HighCard);
+}
+
+#[test]
+fn test_wildcard_as() {
+    let jokers = Ruleset::jokers();
+    let wild = |s: &str| jokers.wildcard_as(&jokers.cards(s).unwrap());
+    assert_eq!(wild("KTJJT"), Some('T'));
+    assert_eq!(wild("2J3J4"), Some('4'));
+    assert_eq!(wild("JJJJJ"), Some('A'));
+    assert_eq!(wild("KTQQT"), None);
+    let jacks = Ruleset::jacks();
+    assert_eq!(jacks.wildcard_as(&jacks.cards("KTJJT").unwrap()), None);
+}
@@ -0,0 +1,413 @@
+//! Camel Cards as a game: every round each player is dealt a hand, bids
+//! chips on it, and scores rank times bid, the puzzle's own scoring.
+//!
+//! Chips are spent for good, so the game is about saving them for strong
+//! hands. After the last round the highest score wins.
+//!
+use std::{
+    fmt,
+    io::{BufRead, Write},
+};
+
+use aoc_core::{rng::Rng, Error};
+
+use crate::{ranks, Hand, HandType, Ruleset};
+
+/// What a player knows when bidding.
+#[derive(Debug)]
+pub struct View<'a> {
+    /// Counting from 1.
+    pub round: usize,
+    pub rounds: usize,
+    pub hand: &'a Hand,
+    pub chips: u64,
+}
+
+impl View<'_> {
+    fn rounds_left(&self) -> u64 {
+        (self.rounds - self.round + 1) as u64
+    }
+}
+
+pub trait Player {
+    fn name(&self) -> String;
+
+    /// Chips to bid on this hand, at most `view.chips`.
+    fn bid(&mut self, view: &View) -> Result<u64, Error>;
+
+    /// Learn how a round went.
+    fn settle(&mut self, _round: &Round) {}
+}
+
+/// Spreads its chips evenly over the rounds.
+pub struct Steady;
+
+impl Player for Steady {
+    fn name(&self) -> String {
+        "steady".to_string()
+    }
+
+    fn bid(&mut self, view: &View) -> Result<u64, Error> {
+        Ok(view.chips / view.rounds_left())
+    }
+}
+
+/// Bids more the better its hand, and everything left in the last round.
+pub struct ByType;
+
+impl Player for ByType {
+    fn name(&self) -> String {
+        "bytype".to_string()
+    }
+
+    fn bid(&mut self, view: &View) -> Result<u64, Error> {
+        if view.round == view.rounds {
+            return Ok(view.chips);
+        }
+        let strength = type_strength(view.hand.hand_type());
+        let max = type_strength(HandType::FiveOfAKind);
+        Ok((view.chips / view.rounds_left() * 2 * strength / max).min(view.chips))
+    }
+}
+
+/// How good a hand of type `t` is to bid on, from 1 for high card to 7
+/// for five of a kind. Poker's own types never come up in Camel Cards, so
+/// they count as the Camel Cards type just below them.
+fn type_strength(t: HandType) -> u64 {
+    match t {
+        HandType::HighCard => 1,
+        HandType::OnePair => 2,
+        HandType::TwoPair => 3,
+        HandType::ThreeOfAKind | HandType::Straight | HandType::Flush => 4,
+        HandType::FullHouse => 5,
+        HandType::FourOfAKind | HandType::StraightFlush => 6,
+        HandType::FiveOfAKind => 7,
+    }
+}
+
+/// Bids at random, with its own seed.
+pub struct Random(pub Rng);
+
+impl Player for Random {
+    fn name(&self) -> String {
+        "random".to_string()
+    }
+
+    fn bid(&mut self, view: &View) -> Result<u64, Error> {
+        Ok(self
+            .0
+            .below(view.chips / view.rounds_left() * 2 + 1)
+            .min(view.chips))
+    }
+}
+
+/// A person answering prompts, such as on stdin and stdout.
+pub struct Human<R, W> {
+    name: String,
+    input: R,
+    output: W,
+}
+
+impl<R: BufRead, W: Write> Human<R, W> {
+    pub fn new(name: &str, input: R, output: W) -> Human<R, W> {
+        Human {
+            name: name.to_string(),
+            input,
+            output,
+        }
+    }
+
+    fn say(&mut self, text: fmt::Arguments) -> Result<(), Error> {
+        self.output
+            .write_fmt(text)
+            .and_then(|_| self.output.flush())
+            .map_err(|e| Error::input(format!("cannot prompt {}: {}", self.name, e)))
+    }
+}
+
+impl<R: BufRead, W: Write> Player for Human<R, W> {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    /// Asks again until the answer is a bid the player can afford.
+    fn bid(&mut self, view: &View) -> Result<u64, Error> {
+        loop {
+            self.say(format_args!(
+                "round {}/{}: {} ({}), {} chips left, bid? ",
+                view.round,
+                view.rounds,
+                view.hand,
+                view.hand.hand_type(),
+                view.chips
+            ))?;
+            let mut line = String::new();
+            match self.input.read_line(&mut line) {
+                Ok(0) => return Err(Error::input("no more bids").with_text(&self.name)),
+                Ok(_) => (),
+                Err(e) => return Err(Error::input(format!("cannot read a bid: {}", e))),
+            }
+            match line.trim().parse::<u64>() {
+                Ok(bid) if bid <= view.chips => return Ok(bid),
+                _ => self.say(format_args!("a bid is a number from 0 to {}\n", view.chips))?,
+            }
+        }
+    }
+}
+
+/// One player's hand and outcome in a round.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Seat {
+    pub player: String,
+    pub hand: Hand,
+    pub bid: u64,
+    pub rank: u64,
+    pub won: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Round {
+    pub number: usize,
+    /// In seating order.
+    pub seats: Vec<Seat>,
+}
+
+impl Round {
+    /// Everything paid out this round.
+    pub fn total(&self) -> u64 {
+        self.seats.iter().map(|s| s.won).sum()
+    }
+}
+
+impl fmt::Display for Round {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "round {}", self.number)?;
+        for s in &self.seats {
+            writeln!(
+                f,
+                "  {:<10} {}  {:<15} bid {:>5}  rank {:>2}  won {:>6}",
+                s.player,
+                s.hand,
+                s.hand.hand_type(),
+                s.bid,
+                s.rank,
+                s.won
+            )?;
+        }
+        Ok(())
+    }
+}
+
+struct Entry<'a> {
+    player: Box<dyn Player + 'a>,
+    chips: u64,
+    score: u64,
+}
+
+/// Players, their chips and scores, and the dealer's shuffle.
+pub struct Tournament<'a> {
+    rules: Ruleset,
+    rng: Rng,
+    entries: Vec<Entry<'a>>,
+    chips: u64,
+    rounds: usize,
+    played: usize,
+}
+
+impl<'a> Tournament<'a> {
+    /// `rounds` rounds with every player starting on `chips`, hands dealt
+    /// from `seed` so the same seed deals the same game.
+    pub fn new(rules: Ruleset, seed: u64, rounds: usize, chips: u64) -> Tournament<'a> {
+        Tournament {
+            rules,
+            rng: Rng::new(seed),
+            entries: Vec::new(),
+            chips,
+            rounds,
+            played: 0,
+        }
+    }
+
+    pub fn with_player(mut self, player: Box<dyn Player + 'a>) -> Tournament<'a> {
+        self.entries.push(Entry {
+            player,
+            chips: self.chips,
+            score: 0,
+        });
+        self
+    }
+
+    /// Deal, take bids in seating order, then rank and pay out. `None`
+    /// once every round is played.
+    pub fn play_round(&mut self) -> Result<Option<Round>, Error> {
+        if self.played == self.rounds || self.entries.is_empty() {
+            return Ok(None);
+        }
+        self.played += 1;
+        let hands = (0..self.entries.len())
+            .map(|_| self.rules.random_hand(&mut self.rng))
+            .collect::<Vec<_>>();
+        let mut bids = Vec::with_capacity(hands.len());
+        for (entry, hand) in self.entries.iter_mut().zip(&hands) {
+            let view = View {
+                round: self.played,
+                rounds: self.rounds,
+                hand,
+                chips: entry.chips,
+            };
+            let bid = entry.player.bid(&view)?;
+            if bid > entry.chips {
+                return Err(
+                    Error::solve(format!("bid {} with only {} chips", bid, entry.chips))
+                        .with_text(entry.player.name()),
+                );
+            }
+            entry.chips -= bid;
+            bids.push(bid);
+        }
+
+        // equal hands keep their seating order
+        let ranks = ranks(&hands);
+        let seats = self
+            .entries
+            .iter_mut()
+            .zip(hands)
+            .zip(bids.iter().zip(ranks))
+            .map(|((entry, hand), (&bid, rank))| {
+                entry.score += rank * bid;
+                Seat {
+                    player: entry.player.name(),
+                    hand,
+                    bid,
+                    rank,
+                    won: rank * bid,
+                }
+            })
+            .collect::<Vec<_>>();
+        let round = Round {
+            number: self.played,
+            seats,
+        };
+        for entry in self.entries.iter_mut() {
+            entry.player.settle(&round);
+        }
+        Ok(Some(round))
+    }
+
+    /// Play every round left, handing each to `watch`.
+    pub fn play(&mut self, mut watch: impl FnMut(&Round)) -> Result<(), Error> {
+        while let Some(round) = self.play_round()? {
+            watch(&round);
+        }
+        Ok(())
+    }
+
+    /// Names and scores, best first.
+    pub fn standings(&self) -> Vec<(String, u64)> {
+        let mut standings = self
+            .entries
+            .iter()
+            .map(|e| (e.player.name(), e.score))
+            .collect::<Vec<_>>();
+        standings.sort_by_key(|s| std::cmp::Reverse(s.1));
+        standings
+    }
+}
+
+#[cfg(test)]
+fn bots(seed: u64) -> Tournament<'static> {
+    Tournament::new(Ruleset::jokers(), seed, 20, 1000)
+        .with_player(Box::new(Steady))
+        .with_player(Box::new(ByType))
+        .with_player(Box::new(Random(Rng::new(seed + 1))))
+}
+
+#[test]
+fn test_rounds_settle_like_calculate_bids() {
+    let mut game = bots(7);
+    let mut rounds = Vec::new();
+    game.play(|r| rounds.push(r.clone())).unwrap();
+    assert_eq!(rounds.len(), 20);
+    for round in &rounds {
+        let sets = round
+            .seats
+            .iter()
+            .map(|s| (s.hand.clone(), s.bid))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            crate::calculate_bids(sets, &Ruleset::jokers()),
+            Ok(round.total())
+        );
+    }
+    let standings = game.standings();
+    assert_eq!(standings.len(), 3);
+    assert!(standings[0].1 >= standings[2].1);
+    for entry in &game.entries {
+        let spent = rounds
+            .iter()
+            .flat_map(|r| &r.seats)
+            .filter(|s| s.player == entry.player.name())
+            .map(|s| s.bid)
+            .sum::<u64>();
+        assert_eq!(entry.chips + spent, 1000);
+    }
+    assert_eq!(game.play_round(), Ok(None));
+}
+
+#[test]
+fn test_same_seed_same_game() {
+    let play = |seed| {
+        let mut rounds = Vec::new();
+        bots(seed).play(|r| rounds.push(r.clone())).unwrap();
+        rounds
+    };
+    assert_eq!(play(3), play(3));
+    assert_ne!(play(3), play(4));
+}
+
+#[test]
+fn test_human() {
+    let input = "lots\n2000\n600\n".as_bytes();
+    let mut output = Vec::new();
+    let mut game = Tournament::new(Ruleset::jacks(), 1, 2, 1000)
+        .with_player(Box::new(Human::new("you", input, &mut output)))
+        .with_player(Box::new(Steady));
+    let round = game.play_round().unwrap().unwrap();
+    assert_eq!(round.seats[0].bid, 600);
+    assert_eq!(round.seats[1].bid, 500);
+    // the second round finds no more answers
+    assert!(game.play_round().is_err());
+    drop(game);
+    let output = String::from_utf8(output).unwrap();
+    assert_eq!(
+        output.matches("a bid is a number from 0 to 1000").count(),
+        2
+    );
+    assert!(output.contains("round 1/2: "));
+}
+
+#[test]
+fn test_overbidding_bot() {
+    struct Greedy;
+    impl Player for Greedy {
+        fn name(&self) -> String {
+            "greedy".to_string()
+        }
+        fn bid(&mut self, view: &View) -> Result<u64, Error> {
+            Ok(view.chips + 1)
+        }
+    }
+    let mut game = Tournament::new(Ruleset::jacks(), 1, 1, 10).with_player(Box::new(Greedy));
+    let e = game.play_round().unwrap_err();
+    assert_eq!(e.diagnostic().text.as_deref(), Some("greedy"));
+}
+
+#[test]
+fn test_type_strength() {
+    assert_eq!(type_strength(HandType::HighCard), 1);
+    assert_eq!(type_strength(HandType::FiveOfAKind), 7);
+    assert_eq!(
+        type_strength(HandType::Flush),
+        type_strength(HandType::ThreeOfAKind)
+    );
+}
@@ -0,0 +1,4 @@
+aoc_core::examples! {
+    day07::Day07;
+    example: "example.txt", part1 = 6440, part2 = 5905;
+}
@@ -0,0 +1,138 @@
+//! Laws hand comparison must obey for sorting to be sound, checked on many
+//! generated hands. A failure names the seed and hands that broke the law.
+
+use std::cmp::Ordering;
+
+use aoc_core::rng::Rng;
+use day07::{poker, Classifier, Hand, HandType, Ruleset};
+
+const CASES: usize = 2000;
+
+/// Run `law` on `CASES` generated values, each from its own seed so a
+/// failure can be replayed alone.
+fn forall<T: std::fmt::Debug>(
+    name: &str,
+    generate: impl Fn(&mut Rng) -> T,
+    law: impl Fn(&T) -> bool,
+) {
+    for seed in 0..CASES as u64 {
+        let value = generate(&mut Rng::new(seed));
+        assert!(law(&value), "{} fails for seed {}: {:?}", name, seed, value);
+    }
+}
+
+/// Rulesets to test with, and the cards each one spells hands from.
+fn rulesets() -> Vec<(Ruleset, &'static str)> {
+    vec![
+        (Ruleset::jacks(), "23456789TJQKA"),
+        (Ruleset::jokers(), "J23456789TQKA"),
+        (
+            Ruleset::new("abcdef")
+                .and_then(|r| r.with_wildcard('c'))
+                .and_then(|r| r.with_hand_len(7))
+                .unwrap(),
+            "abcdef",
+        ),
+        (
+            Ruleset::jacks().with_classifier(Classifier::Plain),
+            "23456789TJQKA",
+        ),
+    ]
+}
+
+/// Hands drawn from a few cards of `alphabet`, so pairs, ties and equal
+/// hands come up often.
+fn hand(rng: &mut Rng, rules: &Ruleset, alphabet: &str) -> Hand {
+    let mut cards = alphabet.chars().collect::<Vec<_>>();
+    rng.shuffle(&mut cards);
+    cards.truncate(1 + rng.below(4) as usize);
+    let s = (0..rules.hand_len())
+        .map(|_| *rng.pick(&cards))
+        .collect::<String>();
+    Hand::parse(&s, rules).unwrap()
+}
+
+fn hands<const N: usize>(rng: &mut Rng) -> (usize, [Hand; N]) {
+    let sets = rulesets();
+    let which = rng.below(sets.len() as u64) as usize;
+    let (rules, alphabet) = &sets[which];
+    (which, std::array::from_fn(|_| hand(rng, rules, alphabet)))
+}
+
+#[test]
+fn comparison_is_reflexive_and_antisymmetric() {
+    forall("antisymmetry", hands::<2>, |(_, [a, b])| {
+        a.cmp(a) == Ordering::Equal
+            && a.cmp(b) == b.cmp(a).reverse()
+            && (a.cmp(b) == Ordering::Equal) == (a == b)
+    });
+}
+
+#[test]
+fn comparison_is_transitive() {
+    forall("transitivity", hands::<3>, |(_, [a, b, c])| {
+        let mut sorted = [a, b, c];
+        sorted.sort();
+        sorted[0] <= sorted[2] && !(a <= b && b <= c && a > c)
+    });
+}
+
+#[test]
+fn packed_keys_order_like_hands() {
+    forall("key order", hands::<2>, |(_, [a, b])| {
+        a.cmp(b) == a.key().cmp(&b.key())
+    });
+}
+
+#[test]
+fn jokers_never_lower_the_type() {
+    let jacks = Ruleset::jacks();
+    let jokers = Ruleset::jokers();
+    forall(
+        "joker upgrade",
+        |rng| hand(rng, &jacks, "23456789TJQKA"),
+        |h| {
+            let upgraded = h.with_rules(&jokers).unwrap();
+            upgraded.hand_type() >= h.hand_type()
+                && (h.to_string().contains('J') || upgraded.hand_type() == h.hand_type())
+        },
+    );
+}
+
+#[test]
+fn wildcard_substitution_reaches_the_type() {
+    let jokers = Ruleset::jokers();
+    forall(
+        "wildcard substitution",
+        |rng| hand(rng, &jokers, "J23456789TQKA"),
+        |h| match h.wildcard_as() {
+            None => !h.to_string().contains('J'),
+            Some(c) => {
+                let s = h.to_string().replace('J', &c.to_string());
+                s.parse::<Hand>().unwrap().hand_type() == h.hand_type()
+            }
+        },
+    );
+}
+
+/// Seven distinct cards off a shuffled deck.
+fn poker_hands(rng: &mut Rng) -> [Hand; 2] {
+    let mut deck = "23456789TJQKA"
+        .chars()
+        .flat_map(|r| "cdhs".chars().map(move |s| format!("{}{}", r, s)))
+        .collect::<Vec<_>>();
+    std::array::from_fn(|_| {
+        rng.shuffle(&mut deck);
+        poker::hand(&deck[..7].concat()).unwrap()
+    })
+}
+
+#[test]
+fn poker_comparison_obeys_the_same_laws() {
+    forall("poker", poker_hands, |[a, b]| {
+        a.cmp(b) == b.cmp(a).reverse()
+            && (a.cmp(b) == Ordering::Equal) == (a == b)
+            && a.cmp(b) == a.key().cmp(&b.key())
+            && a.hand_type() != HandType::FiveOfAKind
+    });
+}
@@ -0,0 +1,224 @@
+//! The network as a Graphviz graph or JSON, to look at an input's shape and
+//! check the cycles the ghosts end up in.
+//!
+use std::{collections::HashMap, fmt::Write, str::FromStr};
+
+use aoc_core::json;
+
+use crate::{
+    ghosts::{self, Orbit},
+    Documents, Node, Pattern, Turn,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Dot,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "dot" => Ok(Format::Dot),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("format must be dot or json, got {:?}", s)),
+        }
+    }
+}
+
+/// Edge colours of successive ghosts' cycles.
+const COLORS: [&str; 6] = ["red", "blue", "darkgreen", "orange", "purple", "brown"];
+
+/// What to draw besides the nodes and their L and R exits.
+pub struct Highlight<'a> {
+    pub starts: &'a Pattern,
+    pub goals: &'a Pattern,
+    /// Also walk a ghost from every start and mark the cycle it ends in.
+    pub cycles: bool,
+}
+
+pub fn render(docs: &Documents, highlight: &Highlight, format: Format) -> String {
+    let net = docs.network();
+    let orbits = match highlight.cycles {
+        true => highlight
+            .starts
+            .nodes(net)
+            .map(|start| ghosts::orbit(net, docs.turns(), start))
+            .collect(),
+        false => Vec::new(),
+    };
+    match format {
+        Format::Dot => dot(docs, highlight, &orbits),
+        Format::Json => json(docs, highlight, &orbits),
+    }
+}
+
+/// The exit each step of a cycle takes, and the first ghost to take it.
+fn cycle_edges(orbits: &[Orbit], turns: &[Turn]) -> HashMap<(Node, Turn), usize> {
+    let mut edges = HashMap::new();
+    for (ghost, orbit) in orbits.iter().enumerate() {
+        for (t, &node) in orbit.path.iter().enumerate().skip(orbit.entered) {
+            edges.entry((node, turns[t % turns.len()])).or_insert(ghost);
+        }
+    }
+    edges
+}
+
+fn dot(docs: &Documents, highlight: &Highlight, orbits: &[Orbit]) -> String {
+    let net = docs.network();
+    let edges = cycle_edges(orbits, docs.turns());
+    // names are letters, digits and underscores, so quoting is enough
+    let mut out = "digraph network {\n".to_string();
+    for (orbit, color) in orbits.iter().zip(COLORS.iter().cycle()) {
+        let _ = writeln!(
+            out,
+            "  // {} enters a cycle of {} steps at step {} ({})",
+            net.name(orbit.path[0]),
+            orbit.period(),
+            orbit.entered,
+            color
+        );
+    }
+    for node in net.nodes() {
+        let name = net.name(node);
+        let fill = match (
+            highlight.starts.matches(name),
+            highlight.goals.matches(name),
+        ) {
+            (true, true) => "gold",
+            (true, false) => "palegreen",
+            (false, true) => "lightpink",
+            (false, false) => "",
+        };
+        match fill {
+            "" => {
+                let _ = writeln!(out, "  \"{}\";", name);
+            }
+            fill => {
+                let _ = writeln!(out, "  \"{}\" [style=filled, fillcolor={}];", name, fill);
+            }
+        }
+    }
+    for node in net.nodes() {
+        let [left, right] = [0, 1].map(|turn| net.step(node, turn));
+        // one edge for both exits when they agree and are drawn alike
+        let exits = match left == right && edges.get(&(node, 0)) == edges.get(&(node, 1)) {
+            true => vec![("L,R", 0)],
+            false => vec![("L", 0), ("R", 1)],
+        };
+        for (label, turn) in exits {
+            let _ = write!(
+                out,
+                "  \"{}\" -> \"{}\" [label=\"{}\"",
+                net.name(node),
+                net.name(net.step(node, turn)),
+                label
+            );
+            if let Some(&ghost) = edges.get(&(node, turn)) {
+                let _ = write!(out, ", color={}, penwidth=2", COLORS[ghost % COLORS.len()]);
+            }
+            out.push_str("];\n");
+        }
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn json(docs: &Documents, highlight: &Highlight, orbits: &[Orbit]) -> String {
+    let net = docs.network();
+    let turns = docs
+        .turns()
+        .iter()
+        .map(|&turn| ['L', 'R'][turn as usize])
+        .collect::<String>();
+    let names = |nodes: &[Node]| {
+        let names = nodes
+            .iter()
+            .map(|&node| json::string(net.name(node)))
+            .collect::<Vec<_>>();
+        format!("[{}]", names.join(", "))
+    };
+    let mut out = format!(
+        "{{\n  \"instructions\": {},\n  \"nodes\": [",
+        json::string(&turns)
+    );
+    for node in net.nodes() {
+        let name = net.name(node);
+        let _ = write!(
+            out,
+            "{}\n    {{\"name\": {}, \"left\": {}, \"right\": {}, \"start\": {}, \"goal\": {}}}",
+            if node == 0 { "" } else { "," },
+            json::string(name),
+            json::string(net.name(net.step(node, 0))),
+            json::string(net.name(net.step(node, 1))),
+            highlight.starts.matches(name),
+            highlight.goals.matches(name)
+        );
+    }
+    out.push_str(if net.is_empty() { "],\n" } else { "\n  ],\n" });
+    out.push_str("  \"cycles\": [");
+    for (ghost, orbit) in orbits.iter().enumerate() {
+        let _ = write!(
+            out,
+            "{}\n    {{\"start\": {}, \"entered\": {}, \"period\": {}, \"nodes\": {}}}",
+            if ghost == 0 { "" } else { "," },
+            json::string(net.name(orbit.path[0])),
+            orbit.entered,
+            orbit.period(),
+            names(orbit.cycle())
+        );
+    }
+    out.push_str(if orbits.is_empty() {
+        "]\n}\n"
+    } else {
+        "\n  ]\n}\n"
+    });
+    out
+}
+
+#[cfg(test)]
+fn example(format: Format, cycles: bool) -> String {
+    let docs = crate::parse_all(include_str!("../fixtures/ghosts.txt")).unwrap();
+    let suffix = |s: &str| Pattern::Suffix(s.to_string());
+    let highlight = Highlight {
+        starts: &suffix("A"),
+        goals: &suffix("Z"),
+        cycles,
+    };
+    render(&docs, &highlight, format)
+}
+
+#[test]
+fn test_dot() {
+    let dot = example(Format::Dot, false);
+    assert!(dot.starts_with("digraph network {\n  \"11A\" [style=filled, fillcolor=palegreen];\n"));
+    assert!(dot.contains("  \"11Z\" [style=filled, fillcolor=lightpink];\n"));
+    assert!(
+        dot.contains("  \"11A\" -> \"11B\" [label=\"L\"];\n  \"11A\" -> \"XXX\" [label=\"R\"];\n")
+    );
+    assert!(dot.contains("  \"22B\" -> \"22C\" [label=\"L,R\"];\n"));
+    assert!(dot.ends_with("}\n"));
+    assert!(!dot.contains("color=red"));
+
+    let dot = example(Format::Dot, true);
+    assert!(dot.contains("// 11A enters a cycle of 2 steps at step 1 (red)"));
+    assert!(dot.contains("// 22A enters a cycle of 6 steps at step 1 (blue)"));
+    assert!(dot.contains("  \"11B\" -> \"11Z\" [label=\"R\", color=red, penwidth=2];\n"));
+    assert!(dot.contains("  \"11B\" -> \"XXX\" [label=\"L\"];\n"));
+    assert!(dot.contains("  \"22Z\" -> \"22B\" [label=\"L,R\", color=blue, penwidth=2];\n"));
+}
+
+#[test]
+fn test_json() {
+    let json = example(Format::Json, true);
+    assert!(json.starts_with("{\n  \"instructions\": \"LR\",\n  \"nodes\": [\n"));
+    assert!(json.contains(
+        "    {\"name\": \"11A\", \"left\": \"11B\", \"right\": \"XXX\", \"start\": true, \"goal\": false},\n"
+    ));
+    assert!(json.contains(
+        "    {\"start\": \"22A\", \"entered\": 1, \"period\": 6, \"nodes\": [\"22B\", \"22C\", \"22Z\", \"22B\", \"22C\", \"22Z\"]}\n  ]\n}\n"
+    ));
+    assert!(example(Format::Json, false).ends_with("  ],\n  \"cycles\": []\n}\n"));
+}
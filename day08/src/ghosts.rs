@@ -0,0 +1,326 @@
+//! When every ghost stands on a Z node at once, without assuming each one
+//! loops back onto its first Z exactly.
+//!
+//! A ghost's state is its node and where it is in the instructions. Both
+//! are finite, so every ghost walks into a cycle: some Z steps happen only
+//! once on the way in, the rest repeat with the cycle's period. Matching
+//! the repeating ones up is the Chinese remainder theorem, for periods that
+//! need not be coprime.
+//!
+use std::fmt;
+
+use aoc_core::Error;
+use num::Integer;
+
+use crate::{Network, Node, Turn};
+
+/// The first step every ghost is on a Z node together, if there is one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arrival {
+    At(u64),
+    Never,
+}
+
+impl fmt::Display for Arrival {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Arrival::At(steps) => write!(f, "{}", steps),
+            Arrival::Never => f.pad("never"),
+        }
+    }
+}
+
+/// Steps one ghost is on a Z node: the listed `times`, and every
+/// `offset + k * period` of the progressions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Schedule {
+    times: Vec<u64>,
+    progressions: Vec<(u64, u64)>,
+}
+
+impl Schedule {
+    fn contains(&self, t: u64) -> bool {
+        self.times.contains(&t)
+            || self
+                .progressions
+                .iter()
+                .any(|&(offset, period)| t >= offset && (t - offset).is_multiple_of(period))
+    }
+
+    /// Steps on both schedules.
+    fn meet(&self, other: &Schedule) -> Result<Schedule, Error> {
+        let mut times = self
+            .times
+            .iter()
+            .filter(|&&t| other.contains(t))
+            .chain(other.times.iter().filter(|&&t| self.contains(t)))
+            .copied()
+            .collect::<Vec<_>>();
+        times.sort_unstable();
+        times.dedup();
+        let mut progressions = Vec::new();
+        for &a in &self.progressions {
+            for &b in &other.progressions {
+                progressions.extend(crt(a, b)?);
+            }
+        }
+        progressions.sort_unstable();
+        progressions.dedup();
+        Ok(Schedule {
+            times,
+            progressions,
+        })
+    }
+
+    fn first(&self) -> Arrival {
+        let first = self.progressions.iter().map(|p| p.0);
+        match self.times.iter().copied().chain(first).min() {
+            Some(t) => Arrival::At(t),
+            None => Arrival::Never,
+        }
+    }
+}
+
+/// Steps matching both `offset + k * period` progressions, as one
+/// progression starting no earlier than either.
+fn crt((a, p): (u64, u64), (b, q): (u64, u64)) -> Result<Option<(u64, u64)>, Error> {
+    let (a, p, b, q) = (a as i128, p as i128, b as i128, q as i128);
+    let egcd = p.extended_gcd(&q);
+    let g = egcd.gcd;
+    if (b - a) % g != 0 {
+        return Ok(None);
+    }
+    let lcm = p / g * q;
+    // p * x = g (mod q), so a + p * x * (b - a) / g = b (mod q)
+    let mut t = a + p * ((b - a) / g * egcd.x).rem_euclid(q / g);
+    let floor = a.max(b);
+    if t < floor {
+        t += (floor - t + lcm - 1) / lcm * lcm;
+    }
+    match (u64::try_from(t), u64::try_from(lcm)) {
+        (Ok(t), Ok(lcm)) => Ok(Some((t, lcm))),
+        _ => Err(Error::solve("the ghosts meet too late to count")),
+    }
+}
+
+/// Where one ghost goes until its node and instruction index repeat.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Orbit {
+    /// The node at each step, from the start to just before the repeat.
+    pub path: Vec<Node>,
+    /// The step the cycle begins at; it runs to the end of `path`.
+    pub entered: usize,
+}
+
+impl Orbit {
+    pub fn period(&self) -> usize {
+        self.path.len() - self.entered
+    }
+
+    pub fn cycle(&self) -> &[Node] {
+        &self.path[self.entered..]
+    }
+}
+
+/// Walk one ghost from `start` until its state repeats.
+pub(crate) fn orbit(net: &Network, turns: &[Turn], start: Node) -> Orbit {
+    // when each node and instruction index was first reached
+    let mut seen = vec![usize::MAX; net.len() * turns.len()];
+    let mut path = Vec::new();
+    let mut node = start;
+    loop {
+        let t = path.len();
+        let idx = t % turns.len();
+        let state = node as usize * turns.len() + idx;
+        if seen[state] != usize::MAX {
+            return Orbit {
+                path,
+                entered: seen[state],
+            };
+        }
+        seen[state] = t;
+        path.push(node);
+        node = net.step(node, turns[idx]);
+    }
+}
+
+/// Every step one ghost lands on a goal node.
+fn schedule(orbit: &Orbit, goals: &[bool]) -> Schedule {
+    let (entered, period) = (orbit.entered as u64, orbit.period() as u64);
+    let hits = (0..)
+        .zip(&orbit.path)
+        .filter(|&(_, &node)| goals[node as usize])
+        .map(|(t, _)| t);
+    let (times, cycle) = hits.partition::<Vec<u64>, _>(|&h| h < entered);
+    Schedule {
+        // standing on a goal at the start is not arriving there
+        times: times.into_iter().filter(|&t| t > 0).collect(),
+        progressions: cycle
+            .into_iter()
+            .map(|h| (if h == 0 { period } else { h }, period))
+            .collect(),
+    }
+}
+
+/// Steps until every ghost from `starts` is on a goal node at the same
+/// time, `goals` telling which nodes are by id.
+pub(crate) fn arrival(
+    net: &Network,
+    turns: &[Turn],
+    starts: impl IntoIterator<Item = Node>,
+    goals: &[bool],
+) -> Result<Arrival, Error> {
+    if turns.is_empty() {
+        return Err(Error::solve("no instructions"));
+    }
+    let mut together: Option<Schedule> = None;
+    for start in starts {
+        let ghost = schedule(&orbit(net, turns, start), goals);
+        let met = match together {
+            Some(s) => s.meet(&ghost)?,
+            None => ghost,
+        };
+        if met.first() == Arrival::Never {
+            return Ok(Arrival::Never);
+        }
+        together = Some(met);
+    }
+    together
+        .map(|s| s.first())
+        .ok_or_else(|| Error::solve("no start node"))
+}
+
+/// Step every ghost at once until they are all on goal nodes, giving up
+/// after `limit` steps. Slow, but obviously right.
+pub fn brute_force(
+    net: &Network,
+    turns: &[Turn],
+    starts: &[Node],
+    goals: &[bool],
+    limit: u64,
+) -> Option<u64> {
+    let mut ghosts = starts.to_vec();
+    for (t, &turn) in (1..=limit).zip(turns.iter().cycle()) {
+        let mut all = true;
+        for node in ghosts.iter_mut() {
+            *node = net.step(*node, turn);
+            all &= goals[*node as usize];
+        }
+        if all {
+            return Some(t);
+        }
+    }
+    None
+}
+
+#[test]
+fn test_crt() {
+    assert_eq!(crt((2, 2), (1, 3)), Ok(Some((4, 6))));
+    assert_eq!(crt((0, 4), (2, 6)), Ok(Some((8, 12))));
+    assert_eq!(crt((1, 4), (2, 6)), Ok(None));
+    // never before either progression starts
+    assert_eq!(crt((10, 5), (0, 1)), Ok(Some((10, 5))));
+    assert_eq!(crt((3, 7), (3, 7)), Ok(Some((3, 7))));
+    let big = 1 << 40;
+    assert!(crt((0, big + 1), (0, big - 1)).is_err());
+}
+
+#[cfg(test)]
+use crate::Pattern;
+
+#[test]
+fn test_arrival() {
+    let net = crate::parse_desert_map(
+        "11A = (11B, 11B)
+11B = (11Z, 11Z)
+11Z = (11C, 11C)
+11C = (11Z, 11Z)
+22A = (22Z, 22Z)
+22Z = (22B, 22B)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+33A = (33Z, 33Z)
+33Z = (33B, 33B)
+33B = (33Z, 33Z)
+44A = (44B, 44B)
+44B = (44C, 44C)
+44C = (44Z, 44Z)
+44Z = (44D, 44D)
+44D = (44D, 44D)",
+    )
+    .unwrap();
+    let goals = Pattern::Suffix("Z".to_string()).mask(&net);
+    let at = |starts: &[&str]| {
+        let starts = starts.iter().map(|s| net.id(s).unwrap());
+        arrival(&net, &[0], starts, &goals)
+    };
+    // Z every 2 steps from 2, and every 3 steps from 1: the first-Z LCM
+    // would say 2
+    assert_eq!(at(&["11A", "22A"]), Ok(Arrival::At(4)));
+    // even steps against odd steps
+    assert_eq!(at(&["11A", "33A"]), Ok(Arrival::Never));
+    // one Z on the way into a cycle without any
+    assert_eq!(at(&["44A"]), Ok(Arrival::At(3)));
+    assert_eq!(at(&["22A", "44A"]), Ok(Arrival::Never));
+    assert_eq!(at(&["33A", "44A"]), Ok(Arrival::At(3)));
+    // starting on a goal that comes round again
+    let goals = Pattern::Suffix("B".to_string()).mask(&net);
+    let b = net.id("33B").unwrap();
+    assert_eq!(arrival(&net, &[0], [b], &goals), Ok(Arrival::At(2)));
+    assert!(at(&[]).is_err());
+    assert_eq!(Arrival::Never.to_string(), "never");
+}
+
+/// Random small maps, where brute force always finds the answer or shows
+/// there is none within the bound of every ghost's cycle.
+#[test]
+fn test_arrival_like_brute_force() {
+    use aoc_core::rng::Rng;
+    let mut found = 0;
+    for seed in 0..300 {
+        let mut rng = Rng::new(seed);
+        let n = 2 + rng.below(8);
+        let names = (0..n)
+            .map(|i| format!("{:02}{}", i, *rng.pick(&['A', 'B', 'Z', 'Z'])))
+            .collect::<Vec<_>>();
+        let map = names
+            .iter()
+            .map(|name| {
+                let (l, r) = (rng.pick(&names), rng.pick(&names));
+                format!("{} = ({}, {})\n", name, l, r)
+            })
+            .collect::<String>();
+        let net = crate::parse_desert_map(&map).unwrap();
+        let turns = (0..1 + rng.below(4))
+            .map(|_| rng.below(2) as Turn)
+            .collect::<Vec<_>>();
+        let starts = Pattern::Suffix("A".to_string())
+            .nodes(&net)
+            .collect::<Vec<_>>();
+        // sometimes the ghosts start on goals
+        let goals = match rng.below(2) {
+            0 => Pattern::Suffix("Z".to_string()),
+            _ => "regex:[AZ]$".parse().unwrap(),
+        };
+        let goals = goals.mask(&net);
+        if starts.is_empty() {
+            continue;
+        }
+        // n^k states with k ghosts, so this is past every joint cycle
+        let limit = (n * turns.len() as u64).pow(starts.len() as u32) + 1;
+        let expected = match brute_force(&net, &turns, &starts, &goals, limit) {
+            Some(t) => Arrival::At(t),
+            None => Arrival::Never,
+        };
+        found += (expected != Arrival::Never) as usize;
+        assert_eq!(
+            arrival(&net, &turns, starts.iter().copied(), &goals),
+            Ok(expected),
+            "seed {}: {:?}\n{}",
+            seed,
+            turns,
+            map
+        );
+    }
+    assert!(found > 50);
+}
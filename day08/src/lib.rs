@@ -0,0 +1,510 @@
+//! https://adventofcode.com/2023/day/8
+//!
+use std::collections::HashMap;
+
+use aoc_core::{Error, Solution};
+
+pub mod export;
+mod ghosts;
+mod pattern;
+
+pub use ghosts::{brute_force, Arrival, Orbit};
+pub use pattern::Pattern;
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+
+    type Input = Documents;
+    type Answer1 = u64;
+    type Answer2 = Arrival;
+
+    fn parse(input: &str) -> Result<Documents, Error> {
+        parse_all(input)
+    }
+
+    fn part1(docs: &Documents) -> Result<u64, Error> {
+        inst_count(
+            &docs.network,
+            &docs.turns,
+            "AAA",
+            &Pattern::Exact("ZZZ".to_string()),
+        )
+    }
+
+    fn part2(docs: &Documents) -> Result<Arrival, Error> {
+        calculate_p2(docs)
+    }
+}
+
+// Data representation
+// -------------------
+//
+// To represent AAA = (BBB, CCC), every name gets a dense id the first time
+// it shows up, and the ids a node leads to sit at its index of a table:
+//
+//     names: ["AAA", "BBB", "CCC"]    next: [[1, 2], ...]
+//              ^^^ id 0                       ^^^^^^ left and right of 0
+//
+type State = String;
+
+/// A node's id, its index into the tables of a [`Network`].
+pub type Node = u32;
+
+/// Which of a node's two exits to take: 0 for L, 1 for R.
+pub type Turn = u8;
+
+/// The map, with every name stored once.
+#[derive(Debug, Default)]
+pub struct Network {
+    names: Vec<String>,
+    ids: HashMap<String, Node>,
+    next: Vec<[Node; 2]>,
+}
+
+impl Network {
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn id(&self, name: &str) -> Option<Node> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, node: Node) -> &str {
+        &self.names[node as usize]
+    }
+
+    /// Every node, in the order their names first appear.
+    pub fn nodes(&self) -> impl Iterator<Item = Node> {
+        0..self.len() as Node
+    }
+
+    #[inline]
+    pub fn step(&self, node: Node, turn: Turn) -> Node {
+        self.next[node as usize][turn as usize]
+    }
+
+    fn intern(&mut self, name: String) -> Node {
+        if let Some(node) = self.id(&name) {
+            return node;
+        }
+        let node = self.names.len() as Node;
+        self.ids.insert(name.clone(), node);
+        self.names.push(name);
+        self.next.push([node, node]);
+        node
+    }
+}
+
+fn parse_desert_map(raw_text: &str) -> Result<Network, Error> {
+    let mut net = Network::default();
+    // where each node is first named, and whether it got defined
+    let mut named_at = Vec::new();
+    let mut defined = Vec::new();
+    for (line, line_idx) in raw_text.lines().zip(1..) {
+        let ((name, col), [left, right]) = match parse_map_pair(line) {
+            Ok(Some(pair)) => pair,
+            Ok(None) => continue,
+            Err(e) => return Err(e.at_line(line_idx)),
+        };
+        let [node, left, right] = [(name, col), left, right].map(|(name, col)| {
+            let node = net.intern(name);
+            if named_at.len() < net.len() {
+                named_at.push((line_idx, col));
+                defined.push(false);
+            }
+            node
+        });
+        if defined[node as usize] {
+            return Err(Error::parse("node defined twice")
+                .at(line_idx, col)
+                .with_text(net.name(node)));
+        }
+        defined[node as usize] = true;
+        net.next[node as usize] = [left, right];
+    }
+    match net.nodes().find(|&node| !defined[node as usize]) {
+        Some(node) => {
+            let (line, col) = named_at[node as usize];
+            Err(Error::parse("undefined node")
+                .at(line, col)
+                .with_text(net.name(node)))
+        }
+        None => Ok(net),
+    }
+}
+
+#[test]
+fn test_parse_map_pair() {
+    let test_text = "AAA = (BBB, CCC)";
+    let ((s, _), [(l, _), (r, lcol)]) = parse_map_pair(test_text).unwrap().unwrap();
+    assert_eq!(s, "AAA");
+    assert_eq!(l, "BBB");
+    assert_eq!(r, "CCC");
+    assert_eq!(lcol, 13);
+    let (node, exits) = parse_map_pair("  start=(a_1 ,Z)   # ends here")
+        .unwrap()
+        .unwrap();
+    assert_eq!(node, ("start".to_string(), 3));
+    assert_eq!(exits, [("a_1".to_string(), 10), ("Z".to_string(), 15)]);
+    assert_eq!(parse_map_pair("   # nothing but a comment"), Ok(None));
+    assert_eq!(parse_map_pair(""), Ok(None));
+    let column = |line| parse_map_pair(line).unwrap_err().diagnostic().column;
+    assert_eq!(column("AAA = (BBB, CC"), Some(15));
+    assert_eq!(column("AAA (BBB, CCC)"), Some(5));
+    assert_eq!(column("AAA = (BBB, )"), Some(13));
+    assert_eq!(column("AAA = (BBB, CCC) DDD"), Some(18));
+    assert_eq!(column("= (BBB, CCC)"), Some(1));
+}
+
+/// A node name and the column it starts at.
+type Name = (State, usize);
+
+/// `NAME = (LEFT, RIGHT)`, or `None` when the line holds nothing but
+/// blanks and a `#` comment.
+fn parse_map_pair(line_text: &str) -> Result<Option<(Name, [Name; 2])>, Error> {
+    let mut scan = Scanner {
+        text: line_text,
+        chars: line_text.chars().collect(),
+        at: 0,
+    };
+    if scan.peek().is_none() {
+        return Ok(None);
+    }
+    let state = scan.name()?;
+    scan.symbol('=')?;
+    scan.symbol('(')?;
+    let left_transition = scan.name()?;
+    scan.symbol(',')?;
+    let right_transition = scan.name()?;
+    scan.symbol(')')?;
+    match scan.peek() {
+        None => Ok(Some((state, [left_transition, right_transition]))),
+        Some(_) => Err(scan.expected("the end of the line")),
+    }
+}
+
+/// Reads one map line a token at a time, counting columns in chars.
+struct Scanner<'a> {
+    text: &'a str,
+    chars: Vec<char>,
+    at: usize,
+}
+
+impl Scanner<'_> {
+    /// The next char that is not blank, `None` at the end of the line or
+    /// a comment.
+    fn peek(&mut self) -> Option<char> {
+        while self.chars.get(self.at).is_some_and(|c| c.is_whitespace()) {
+            self.at += 1;
+        }
+        self.chars.get(self.at).copied().filter(|&c| c != '#')
+    }
+
+    fn name(&mut self) -> Result<Name, Error> {
+        self.peek();
+        let start = self.at;
+        while self
+            .chars
+            .get(self.at)
+            .is_some_and(|&c| c.is_alphanumeric() || c == '_')
+        {
+            self.at += 1;
+        }
+        match start == self.at {
+            true => Err(self.expected("a node name")),
+            false => Ok((self.chars[start..self.at].iter().collect(), start + 1)),
+        }
+    }
+
+    fn symbol(&mut self, symbol: char) -> Result<(), Error> {
+        match self.peek() {
+            Some(c) if c == symbol => {
+                self.at += 1;
+                Ok(())
+            }
+            _ => Err(self.expected(&format!("'{}'", symbol))),
+        }
+    }
+
+    fn expected(&self, what: &str) -> Error {
+        Error::parse(format!("expected {}", what))
+            .at_column(self.at + 1)
+            .with_text(self.text)
+    }
+}
+
+#[test]
+fn test_parse_instruction() {
+    assert_eq!(parse_instruction("LRR"), Ok(vec![0, 1, 1]));
+    let e = parse_instruction("LRX").unwrap_err();
+    assert_eq!(e.diagnostic().column, Some(3));
+}
+
+fn parse_instruction(inst: &str) -> Result<Vec<Turn>, Error> {
+    inst.chars()
+        .zip(1..)
+        .map(|(c, col)| match c {
+            'L' => Ok(0),
+            'R' => Ok(1),
+            _ => Err(Error::parse("instructions are L or R")
+                .at_column(col)
+                .with_text(c)),
+        })
+        .collect()
+}
+
+fn inst_count(net: &Network, turns: &[Turn], init_v: &str, goal: &Pattern) -> Result<u64, Error> {
+    let mut node = net
+        .id(init_v)
+        .ok_or_else(|| Error::solve("undefined node").with_text(init_v))?;
+    if turns.is_empty() {
+        return Err(Error::solve("no instructions"));
+    }
+    let goals = goal.mask(net);
+    // past this many steps some node and instruction came round again
+    let limit = (net.len() * turns.len()) as u64;
+    for steps in 1..=limit {
+        node = net.step(node, turns[((steps - 1) % turns.len() as u64) as usize]);
+        if goals[node as usize] {
+            return Ok(steps);
+        }
+    }
+    Err(Error::solve(format!("never reaches {}", goal)).with_text(init_v))
+}
+
+#[test]
+fn test_inst_count() {
+    let map = "AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)";
+    let net = parse_desert_map(map).unwrap();
+    let zzz = Pattern::Exact("ZZZ".to_string());
+    assert_eq!(Ok(2), inst_count(&net, &[1, 0], "AAA", &zzz));
+    assert!(inst_count(&net, &[0], "QQQ", &zzz).is_err());
+    assert!(inst_count(&net, &[], "AAA", &zzz).is_err());
+    let e = inst_count(&net, &[0], "AAA", &zzz).unwrap_err();
+    assert_eq!(e.diagnostic().text.as_deref(), Some("AAA"));
+}
+
+#[test]
+fn test_parse_desert_map() {
+    let net = parse_desert_map("AAA = (BBB, AAA)\nBBB = (AAA, BBB)").unwrap();
+    assert_eq!(net.len(), 2);
+    assert_eq!(net.id("BBB"), Some(1));
+    assert_eq!(net.step(0, 0), 1);
+    assert_eq!(net.name(net.step(1, 0)), "AAA");
+    let e = parse_desert_map("AAA = (BBB, AAA)\nBBB = (AAA, CCC)").unwrap_err();
+    assert_eq!(e.diagnostic().line, Some(2));
+    assert_eq!(e.diagnostic().text.as_deref(), Some("CCC"));
+}
+
+#[test]
+fn test_parse_all() {
+    let test_input = "RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)";
+    let docs = parse_all(test_input).unwrap();
+    assert_eq!(docs.turns(), [1, 0]);
+    let net = docs.network();
+    assert_eq!(net.len(), 7);
+    let aaa = net.id("AAA").unwrap();
+    assert_eq!(
+        [net.name(net.step(aaa, 0)), net.name(net.step(aaa, 1))],
+        ["BBB", "CCC"]
+    );
+}
+
+/// The instruction line and the map below it.
+#[derive(Debug)]
+pub struct Documents {
+    turns: Vec<Turn>,
+    network: Network,
+}
+
+impl Documents {
+    pub fn turns(&self) -> &[Turn] {
+        &self.turns
+    }
+
+    pub fn network(&self) -> &Network {
+        &self.network
+    }
+}
+
+/// A line without its `#` comment.
+fn uncommented(line: &str) -> &str {
+    match line.split_once('#') {
+        Some((text, _)) => text,
+        None => line,
+    }
+}
+
+fn parse_all(s: &str) -> Result<Documents, Error> {
+    let mut ls = s
+        .lines()
+        .zip(1..)
+        .skip_while(|(l, _)| uncommented(l).trim().is_empty());
+    let (turns, line_idx) = match ls.next() {
+        Some((l, line_idx)) => {
+            let text = uncommented(l);
+            let indent = text.chars().take_while(|c| c.is_whitespace()).count();
+            let turns = parse_instruction(text.trim())
+                .map_err(|e| e.offset_columns(indent).at_line(line_idx))?;
+            (turns, line_idx)
+        }
+        None => return Err(Error::parse("missing instruction line").at_line(1)),
+    };
+    let remaining = ls.map(|(x, _)| format!("{}\n", x)).collect::<String>();
+    Ok(Documents {
+        turns,
+        network: parse_desert_map(&remaining).map_err(|e| e.offset_lines(line_idx))?,
+    })
+}
+
+#[test]
+fn test_execute_all() {
+    let test_input = "RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)";
+    assert_eq!(execute_all(test_input, "AAA"), Ok(2));
+    // passing another Z node on the way to ZZZ
+    let test_input = "L
+
+AAA = (11Z, AAA)
+11Z = (ZZZ, ZZZ)
+ZZZ = (ZZZ, ZZZ)";
+    assert_eq!(execute_all(test_input, "AAA"), Ok(2));
+}
+
+/// Steps from `init_v` to ZZZ.
+pub fn execute_all(s: &str, init_v: &str) -> Result<u64, Error> {
+    let docs = parse_all(s)?;
+    inst_count(
+        &docs.network,
+        &docs.turns,
+        init_v,
+        &Pattern::Exact("ZZZ".to_string()),
+    )
+}
+
+/// Steps until ghosts on every node matching `from` all stand on nodes
+/// matching `to` at once.
+pub fn walk(docs: &Documents, from: &Pattern, to: &Pattern) -> Result<Arrival, Error> {
+    let net = &docs.network;
+    let starts = from.nodes(net).collect::<Vec<_>>();
+    if starts.is_empty() {
+        return Err(Error::solve("no node matches").with_text(from.to_string()));
+    }
+    let goals = to.mask(net);
+    if !goals.contains(&true) {
+        return Err(Error::solve("no node matches").with_text(to.to_string()));
+    }
+    ghosts::arrival(net, &docs.turns, starts, &goals)
+}
+
+#[test]
+fn test_walk() {
+    let docs = parse_all(include_str!("../fixtures/ghosts.txt")).unwrap();
+    let walk = |from: &str, to: &str| walk(&docs, &from.parse().unwrap(), &to.parse().unwrap());
+    assert_eq!(walk("suffix:A", "suffix:Z"), Ok(Arrival::At(6)));
+    assert_eq!(walk("11A", "11Z"), Ok(Arrival::At(2)));
+    assert_eq!(walk("22A", "regex:^22[CZ]$"), Ok(Arrival::At(2)));
+    assert_eq!(walk("set:11A,22A", "set:11B,22B"), Ok(Arrival::At(1)));
+    assert_eq!(walk("prefix:1", "22A"), Ok(Arrival::Never));
+    assert!(walk("QQQ", "ZZZ").is_err());
+    assert!(walk("11A", "QQQ").is_err());
+}
+
+// part 2 specifics
+fn calculate_p2(docs: &Documents) -> Result<Arrival, Error> {
+    let suffix = |s: &str| Pattern::Suffix(s.to_string());
+    walk(docs, &suffix("A"), &suffix("Z"))
+}
+
+#[test]
+fn test_exec_p2() {
+    let test_text = "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)";
+    assert_eq!(execute_all_p2(test_text), Ok(Arrival::At(6)));
+}
+
+pub fn execute_all_p2(s: &str) -> Result<Arrival, Error> {
+    calculate_p2(&parse_all(s)?)
+}
+
+#[test]
+fn test_parse_all_errors() {
+    let e = parse_all("LRX\n\nAAA = (BBB, CCC)").unwrap_err();
+    assert_eq!(
+        (e.diagnostic().line, e.diagnostic().column),
+        (Some(1), Some(3))
+    );
+    let e = parse_all("LR\n\nAAA = (BBB, CCC)\nBBB = (").unwrap_err();
+    assert_eq!(e.diagnostic().line, Some(4));
+    assert!(parse_all("").is_err());
+    let e = parse_all("# directions\n  LR X\n").unwrap_err();
+    assert_eq!(
+        (e.diagnostic().line, e.diagnostic().column),
+        (Some(2), Some(5))
+    );
+    let e = parse_all("LR\n\nAAA = (BBB, AAA)\n\nBBB = (AAA, CCC)").unwrap_err();
+    assert_eq!(
+        (e.diagnostic().line, e.diagnostic().column),
+        (Some(5), Some(13))
+    );
+    let e = parse_all("LR\n\nAAA = (AAA, AAA)\n AAA = (AAA, AAA)").unwrap_err();
+    assert_eq!(
+        (e.diagnostic().line, e.diagnostic().column),
+        (Some(4), Some(2))
+    );
+}
+
+#[test]
+fn test_parse_all_flexible() {
+    let docs = parse_all(
+        "# a map with long names
+RL   # right, then left
+
+start   = ( middle,finish )
+middle = (finish, start)  # back again
+finish=(finish,finish)
+",
+    )
+    .unwrap();
+    assert_eq!(docs.turns(), [1, 0]);
+    let net = docs.network();
+    let start = net.id("start").unwrap();
+    assert_eq!(net.name(net.step(start, 1)), "finish");
+    assert_eq!(net.len(), 3);
+}
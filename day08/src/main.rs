@@ -1,184 +1,100 @@
-//! https://adventofcode.com/2023/day/8
+//! Without arguments, print both answers. With `--from`, `--to` or
+//! `--input`, print how many steps ghosts starting on every `--from` node
+//! take to stand on `--to` nodes all at once, or `never`.
 //!
-use std::{
-    collections::{HashMap, HashSet},
-    iter::Cycle,
-    ops::ControlFlow,
-    str::Chars,
-};
-
-fn main() {
-    let s = std::fs::read_to_string("day08.in").unwrap();
-    println!("{}", execute_all(&s, "AAA"));
-    println!("{}", execute_all_p2(&s));
-}
-
-// Data representation
-// -------------------
-//
-// To represent AAA = (BBB, CCC), we use hash table, where:
-//              ^^^   ^^^^^^^^^^
-//              key      value
-//
-type Transition = (String, String);
-
-type State = String;
+//! ```text
+//! day08 [--from PATTERN] [--to PATTERN] [--input PATH|-]
+//! day08 --export dot|json [--cycles] [--from PATTERN] [--to PATTERN]
+//!       [--input PATH|-]
+//! ```
+//!
+//! A pattern is a node name, or `prefix:P`, `suffix:S`, `regex:RE` or
+//! `set:A,B,C`. They default to `AAA` and `ZZZ`, part one's question, so
+//! `--from suffix:A --to suffix:Z` asks part two's.
+//!
+//! `--export` prints the whole network instead, with the `--from` and `--to`
+//! nodes highlighted, by default part two's. `--cycles` also marks the
+//! cycle each ghost ends up in.
+//!
+use std::process::ExitCode;
 
-type DesertMap = HashMap<State, Transition>;
+use aoc_core::{
+    args::Args,
+    input::{Resolver, Source},
+    Error, Solution,
+};
+use day08::{
+    export::{self, Format, Highlight},
+    walk, Arrival, Day08, Pattern,
+};
 
-fn parse_desert_map(raw_text: &str) -> DesertMap {
-    let mut desert_map: DesertMap = HashMap::new();
-    for line in raw_text.lines() {
-        let (k, v) = parse_map_pair(line);
-        desert_map.insert(k, v);
+fn main() -> ExitCode {
+    let mut args = Args::from_env();
+    let result = match args.flag("--help") {
+        true => Err(USAGE.to_string()),
+        false => command(args),
+    };
+    match result {
+        Ok(Output::Answers) => aoc_core::run::<Day08>(),
+        Ok(Output::Steps(arrival)) => println!("{}", arrival),
+        Ok(Output::Graph(graph)) => print!("{}", graph),
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
     }
-    desert_map
-}
-
-#[test]
-fn test_parse_map_pair() {
-    let test_text = "AAA = (BBB, CCC)";
-    let (s, (l, r)) = parse_map_pair(test_text);
-    assert_eq!(s, "AAA");
-    assert_eq!(l, "BBB");
-    assert_eq!(r, "CCC");
-}
-
-fn parse_map_pair(line_text: &str) -> (State, Transition) {
-    let state = line_text[0..3].to_string();
-    let left_transition = line_text[7..10].to_string();
-    let right_transition = line_text[12..15].to_string();
-    (state, (left_transition, right_transition))
-}
-
-// To represent instructions as streams (infinity iterator) of String
-type InstStream<'a> = Cycle<Chars<'a>>;
-
-#[test]
-fn test_parse_instruction() {
-    let instructions = "LR";
-    let stream = parse_instruction(instructions);
-    assert_eq!(stream.take(4).collect::<String>(), "LRLR");
-}
-
-fn parse_instruction(inst: &str) -> InstStream<'_> {
-    inst.chars().cycle()
-}
-
-fn inst_count(map: &DesertMap, insts: InstStream, init_v: &str) -> u64 {
-    match insts
-        .zip(1..)
-        .try_fold((0_u64, init_v), |acc, (inst, idx)| {
-            let next = match inst {
-                'L' => map.get(acc.1).unwrap().0.as_str(),
-                'R' => map.get(acc.1).unwrap().1.as_str(),
-                _ => panic!("bad pattern"),
-            };
-            match next.ends_with("Z") {
-                true => ControlFlow::Break(acc.0 + 1),
-                _ => ControlFlow::Continue((acc.0 + 1, next)),
-            }
-        }) {
-        ControlFlow::Break(s) => s,
-        _ => panic!("never happens"),
+    ExitCode::SUCCESS
+}
+
+const USAGE: &str = "usage: day08 [--from PATTERN] [--to PATTERN] [--input PATH|-]
+       day08 --export dot|json [--cycles] [--from PATTERN] [--to PATTERN] [--input PATH|-]
+patterns: NAME, prefix:P, suffix:S, regex:RE, set:A,B,C";
+
+enum Output {
+    Answers,
+    /// The answer to the query on the command line.
+    Steps(Arrival),
+    Graph(String),
+}
+
+fn command(mut args: Args) -> Result<Output, String> {
+    let in_day = |e: Error| e.in_day(Day08::DAY).to_string();
+    let export = args.parsed::<Format>("--export")?;
+    let cycles = args.flag("--cycles");
+    let from = args.value("--from")?;
+    let to = args.value("--to")?;
+    let source = args.parsed::<Source>("--input")?;
+    args.finish()?;
+    if cycles && export.is_none() {
+        return Err("--cycles needs --export".to_string());
     }
-}
-
-#[test]
-fn test_inst_count() {
-    let inst_pat = "RL";
-    let map = "AAA = (BBB, CCC)
-BBB = (DDD, EEE)
-CCC = (ZZZ, GGG)
-DDD = (DDD, DDD)
-EEE = (EEE, EEE)
-GGG = (GGG, GGG)
-ZZZ = (ZZZ, ZZZ)";
-    let desert_map = parse_desert_map(map);
-    let insts = parse_instruction(inst_pat);
-    assert_eq!(2, inst_count(&desert_map, insts, "AAA"));
-}
-
-#[test]
-fn test_parse_all() {
-    let test_input = "RL
-
-AAA = (BBB, CCC)
-BBB = (DDD, EEE)
-CCC = (ZZZ, GGG)
-DDD = (DDD, DDD)
-EEE = (EEE, EEE)
-GGG = (GGG, GGG)
-ZZZ = (ZZZ, ZZZ)";
-    println!("{:?}", parse_all(test_input));
-}
-
-fn parse_all(s: &str) -> Option<(DesertMap, InstStream)> {
-    let mut ls = s.lines();
-    let stream = parse_instruction(ls.next()?);
-    ls.next();
-    let remaining = ls.map(|x| format!("{}\n", x)).collect::<String>();
-    Some((parse_desert_map(&remaining), stream))
-}
-
-#[test]
-fn test_execute_all() {
-    let test_input = "RL
-
-AAA = (BBB, CCC)
-BBB = (DDD, EEE)
-CCC = (ZZZ, GGG)
-DDD = (DDD, DDD)
-EEE = (EEE, EEE)
-GGG = (GGG, GGG)
-ZZZ = (ZZZ, ZZZ)";
-    println!("{}", execute_all(test_input, "AAA"));
-}
-
-fn execute_all(s: &str, init_v: &str) -> u64 {
-    let (dm, inst) = parse_all(s).unwrap();
-    inst_count(&dm, inst, init_v)
-}
-
-// part 2 specifics
-fn find_all_as(m: &DesertMap) -> HashSet<&str> {
-    let mut set = HashSet::new();
-    for k in m.keys() {
-        match k.ends_with("A") {
-            true => {
-                set.insert(k.as_str());
-            }
-            _ => continue,
-        }
+    if (&export, &from, &to, &source) == (&None, &None, &None, &None) {
+        return Ok(Output::Answers);
     }
-    set
-}
-
-fn calculate_p2(m: &DesertMap, insts: InstStream) -> u64 {
-    let start_with_as = find_all_as(m);
-    let mut lcm = 1_u64;
-    for a in start_with_as {
-        lcm = num::integer::lcm(lcm, inst_count(m, insts.clone(), a));
+    let defaults = match export {
+        Some(_) => ("suffix:A", "suffix:Z"),
+        None => ("AAA", "ZZZ"),
+    };
+    let pattern = |s: Option<String>, default: &str| {
+        s.as_deref()
+            .unwrap_or(default)
+            .parse::<Pattern>()
+            .map_err(in_day)
+    };
+    let (from, to) = (pattern(from, defaults.0)?, pattern(to, defaults.1)?);
+    let input = Resolver::from_env()
+        .resolve(Day08::DAY, &source.unwrap_or(Source::Cache))
+        .map_err(in_day)?;
+    let docs = Day08::parse(&input).map_err(in_day)?;
+    match export {
+        Some(format) => {
+            let highlight = Highlight {
+                starts: &from,
+                goals: &to,
+                cycles,
+            };
+            Ok(Output::Graph(export::render(&docs, &highlight, format)))
+        }
+        None => walk(&docs, &from, &to).map(Output::Steps).map_err(in_day),
     }
-    lcm
-}
-
-#[test]
-fn test_exec_p2() {
-    let test_text = "LR
-
-11A = (11B, XXX)
-11B = (XXX, 11Z)
-11Z = (11B, XXX)
-22A = (22B, XXX)
-22B = (22C, 22C)
-22C = (22Z, 22Z)
-22Z = (22B, 22B)
-XXX = (XXX, XXX)";
-    println!("{}", execute_all_p2(test_text))
-}
-
-fn execute_all_p2(s: &str) -> u64 {
-    let (dm, inst) = parse_all(s).unwrap();
-    calculate_p2(&dm, inst)
 }
@@ -0,0 +1,108 @@
+//! Which nodes to start from and which to stop at.
+//!
+use std::{fmt, str::FromStr};
+
+use aoc_core::Error;
+use regex::Regex;
+
+use crate::{Network, Node};
+
+/// A test on node names, written `NAME`, `prefix:P`, `suffix:S`,
+/// `regex:RE` or `set:A,B,C`.
+#[derive(Debug, Clone)]
+pub enum Pattern {
+    Exact(String),
+    Prefix(String),
+    Suffix(String),
+    Regex(Regex),
+    Set(Vec<String>),
+}
+
+impl Pattern {
+    pub fn matches(&self, name: &str) -> bool {
+        match self {
+            Pattern::Exact(s) => name == s,
+            Pattern::Prefix(s) => name.starts_with(s.as_str()),
+            Pattern::Suffix(s) => name.ends_with(s.as_str()),
+            Pattern::Regex(re) => re.is_match(name),
+            Pattern::Set(names) => names.iter().any(|s| s == name),
+        }
+    }
+
+    /// Every node of `net` that matches, in id order.
+    pub fn nodes<'a>(&'a self, net: &'a Network) -> impl Iterator<Item = Node> + 'a {
+        net.nodes().filter(|&node| self.matches(net.name(node)))
+    }
+
+    /// Whether each node of `net` matches, indexed by id.
+    pub fn mask(&self, net: &Network) -> Vec<bool> {
+        net.nodes()
+            .map(|node| self.matches(net.name(node)))
+            .collect()
+    }
+}
+
+impl FromStr for Pattern {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Pattern, Error> {
+        let (kind, arg) = s.split_once(':').unwrap_or(("", s));
+        if arg.is_empty() {
+            return Err(Error::parse("empty pattern").with_text(s));
+        }
+        match kind {
+            "" => Ok(Pattern::Exact(arg.to_string())),
+            "prefix" => Ok(Pattern::Prefix(arg.to_string())),
+            "suffix" => Ok(Pattern::Suffix(arg.to_string())),
+            "regex" => Regex::new(arg).map(Pattern::Regex).map_err(|e| {
+                // the last line says what is wrong, the rest draws it
+                let e = e.to_string();
+                let why = e.lines().last().unwrap_or_default();
+                Error::parse(format!("bad regex, {}", why.trim_start_matches("error: ")))
+                    .with_text(arg)
+            }),
+            "set" => Ok(Pattern::Set(
+                arg.split(',').map(|name| name.trim().to_string()).collect(),
+            )),
+            _ => Err(
+                Error::parse("patterns are NAME, prefix:, suffix:, regex: or set:").with_text(s),
+            ),
+        }
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Pattern::Exact(s) => write!(f, "{}", s),
+            Pattern::Prefix(s) => write!(f, "prefix:{}", s),
+            Pattern::Suffix(s) => write!(f, "suffix:{}", s),
+            Pattern::Regex(re) => write!(f, "regex:{}", re),
+            Pattern::Set(names) => write!(f, "set:{}", names.join(",")),
+        }
+    }
+}
+
+#[test]
+fn test_pattern() {
+    let matching = |pattern: &str| {
+        let pattern = pattern.parse::<Pattern>().unwrap();
+        ["AAA", "ZZZ", "11Z", "AZ"]
+            .into_iter()
+            .filter(|name| pattern.matches(name))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(matching("ZZZ"), ["ZZZ"]);
+    assert_eq!(matching("Z"), Vec::<&str>::new());
+    assert_eq!(matching("suffix:Z"), ["ZZZ", "11Z", "AZ"]);
+    assert_eq!(matching("prefix:A"), ["AAA", "AZ"]);
+    assert_eq!(matching("regex:^[0-9]+Z$"), ["11Z"]);
+    assert_eq!(matching("set:AAA, AZ"), ["AAA", "AZ"]);
+    assert_eq!(
+        "regex:^A.Z$".parse::<Pattern>().unwrap().to_string(),
+        "regex:^A.Z$"
+    );
+    assert!("regex:(".parse::<Pattern>().is_err());
+    assert!("glob:*Z".parse::<Pattern>().is_err());
+    assert!("suffix:".parse::<Pattern>().is_err());
+}
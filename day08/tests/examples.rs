@@ -0,0 +1,6 @@
+aoc_core::examples! {
+    day08::Day08;
+    direct: "direct.txt", part1 = 2;
+    repeated: "repeated.txt", part1 = 6;
+    ghosts: "ghosts.txt", part2 = 6;
+}
@@ -0,0 +1,9 @@
+aoc_core::examples! {
+    day10::Day10;
+    square_loop: "square_loop.txt", part1 = 4;
+    complex_loop: "complex_loop.txt", part1 = 8;
+    enclosed: "enclosed.txt", part2 = 4;
+    squeezed: "squeezed.txt", part2 = 4;
+    larger: "larger.txt", part2 = 8;
+    junk: "junk.txt", part2 = 10;
+}
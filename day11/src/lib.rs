@@ -0,0 +1,150 @@
+//! https://adventofcode.com/2023/day/11
+//!
+use aoc_core::{
+    grid::{self, Grid},
+    Error, Solution,
+};
+use itertools::Itertools;
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+
+    type Input = Cosmo;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Cosmo, Error> {
+        Cosmo::new(input)
+    }
+
+    fn part1(cosmo: &Cosmo) -> Result<usize, Error> {
+        Ok(cosmo.find_all_distances(2))
+    }
+
+    fn part2(cosmo: &Cosmo) -> Result<usize, Error> {
+        Ok(cosmo.find_all_distances(1000000))
+    }
+}
+
+type Weight = u64;
+type Galaxy = (usize, usize);
+
+fn range_between<F>(g1: Galaxy, g2: Galaxy, get_key: F) -> std::ops::Range<usize>
+where
+    F: Fn(Galaxy) -> usize,
+{
+    let k1 = get_key(g1);
+    let k2 = get_key(g2);
+    match k1 > k2 {
+        true => k2..k1,
+        false => k1..k2,
+    }
+}
+
+fn range_between_row(g1: Galaxy, g2: Galaxy) -> std::ops::Range<usize> {
+    range_between(g1, g2, |x| x.0)
+}
+
+fn range_between_col(g1: Galaxy, g2: Galaxy) -> std::ops::Range<usize> {
+    range_between(g1, g2, |x| x.1)
+}
+
+#[derive(Debug)]
+pub struct Cosmo {
+    map: Grid,
+    /// row... col..., set for the ones without any galaxy
+    empty: Vec<bool>,
+    row_count: usize,
+}
+
+impl Cosmo {
+    pub fn col_not_contain_galaxy(map: &Grid, col_idx: usize) -> bool {
+        for v in map {
+            if v[col_idx] == '#' {
+                return false;
+            }
+        }
+        true
+    }
+
+    pub fn new(s: &str) -> Result<Cosmo, Error> {
+        let map = grid::parse_rect(s, ".#")?;
+        let row_count = map.len();
+        let col_count = map[0].len();
+        let mut empty = [false].repeat(row_count + col_count);
+        for (row_idx, row_vec) in map.iter().enumerate() {
+            if row_vec.contains(&'#') {
+                continue;
+            }
+            empty[row_idx] = true;
+        }
+        for col_idx in 0..col_count {
+            if Self::col_not_contain_galaxy(&map, col_idx) {
+                empty[row_count + col_idx] = true;
+            }
+        }
+        Ok(Self {
+            map,
+            empty,
+            row_count,
+        })
+    }
+
+    /// Every empty row and column is replaced by `expansion` of them.
+    fn weight(&self, idx: usize, expansion: Weight) -> Weight {
+        match self.empty[idx] {
+            true => expansion,
+            false => 1,
+        }
+    }
+
+    pub fn find_galaxies(&self) -> Vec<Galaxy> {
+        grid::positions(&self.map, '#').collect()
+    }
+
+    pub fn find_pairs(&self) -> Vec<(Galaxy, Galaxy)> {
+        let gs = self.find_galaxies();
+        gs.into_iter().tuple_combinations().collect()
+    }
+
+    pub fn find_distance(&self, start: Galaxy, end: Galaxy, expansion: Weight) -> usize {
+        let horizon = range_between_col(start, end).fold(0_usize, |acc, x| {
+            acc + self.weight(x + self.row_count, expansion) as usize
+        });
+        let vertical = range_between_row(start, end)
+            .fold(0_usize, |acc, x| acc + self.weight(x, expansion) as usize);
+        vertical + horizon
+    }
+
+    pub fn find_all_distances(&self, expansion: Weight) -> usize {
+        self.find_pairs().iter().fold(0_usize, |acc, x| {
+            acc + self.find_distance(x.0, x.1, expansion)
+        })
+    }
+}
+
+#[test]
+fn test_find_galaxies() {
+    let s = "...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....";
+    let cosmo = Cosmo::new(s).unwrap();
+    assert_eq!(374, cosmo.find_all_distances(2));
+    assert_eq!(1030, cosmo.find_all_distances(10));
+    assert_eq!(8410, cosmo.find_all_distances(100));
+}
+
+#[test]
+fn test_new_cosmo_errors() {
+    let e = Cosmo::new("..#\n.*.").unwrap_err();
+    assert_eq!(e.diagnostic().text.as_deref(), Some("*"));
+}
@@ -0,0 +1,4 @@
+aoc_core::examples! {
+    day11::Day11;
+    example: "example.txt", part1 = 374, part2 = 82000210;
+}
@@ -0,0 +1,418 @@
+//! https://adventofcode.com/2023/day/12
+//!
+use std::fmt;
+
+use aoc_core::{Error, Solution};
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+
+    type Input = Vec<Record>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Vec<Record>, Error> {
+        parse_records(input)
+    }
+
+    fn part1(records: &Vec<Record>) -> Result<u64, Error> {
+        Ok(records.iter().map(Record::arrangements).sum())
+    }
+
+    fn part2(records: &Vec<Record>) -> Result<u64, Error> {
+        Ok(records.iter().map(|r| r.unfold(5).arrangements()).sum())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Spring {
+    Operational,
+    Damaged,
+    Unknown,
+}
+
+impl Spring {
+    fn parse(c: char) -> Option<Spring> {
+        match c {
+            '.' => Some(Spring::Operational),
+            '#' => Some(Spring::Damaged),
+            '?' => Some(Spring::Unknown),
+            _ => None,
+        }
+    }
+
+    pub fn symbol(self) -> char {
+        match self {
+            Spring::Operational => '.',
+            Spring::Damaged => '#',
+            Spring::Unknown => '?',
+        }
+    }
+
+    fn may_be_operational(self) -> bool {
+        self != Spring::Damaged
+    }
+
+    fn may_be_damaged(self) -> bool {
+        self != Spring::Operational
+    }
+}
+
+/// Write springs back in their puzzle notation.
+pub fn render(springs: &[Spring]) -> String {
+    springs.iter().map(|s| s.symbol()).collect()
+}
+
+/// One line of the condition records, e.g. `???.### 1,1,3`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    springs: Vec<Spring>,
+    /// sizes of the contiguous damaged groups, left to right
+    groups: Vec<usize>,
+}
+
+fn parse_records(s: &str) -> Result<Vec<Record>, Error> {
+    s.lines()
+        .zip(1..)
+        .filter(|(line, _)| !line.trim().is_empty())
+        .map(|(line, line_idx)| parse_record(line).map_err(|e| e.at_line(line_idx)))
+        .collect()
+}
+
+fn parse_record(line: &str) -> Result<Record, Error> {
+    let (pattern, groups_text) = line
+        .trim()
+        .split_once(' ')
+        .ok_or(Error::parse("expected springs and group sizes").with_text(line))?;
+    let mut springs = Vec::new();
+    for (c, col) in pattern.chars().zip(1..) {
+        match Spring::parse(c) {
+            Some(spring) => springs.push(spring),
+            None => {
+                return Err(Error::parse("springs are one of .#?")
+                    .at_column(col)
+                    .with_text(c))
+            }
+        }
+    }
+    let mut groups = Vec::new();
+    let mut col = pattern.chars().count() + 2;
+    for size in groups_text.trim().split(',') {
+        match size.trim().parse::<usize>() {
+            Ok(n) if n > 0 => groups.push(n),
+            _ => {
+                return Err(Error::parse("group sizes are positive numbers")
+                    .at_column(col)
+                    .with_text(size))
+            }
+        }
+        col += size.chars().count() + 1;
+    }
+    Ok(Record { springs, groups })
+}
+
+impl Record {
+    pub fn springs(&self) -> &[Spring] {
+        &self.springs
+    }
+
+    pub fn groups(&self) -> &[usize] {
+        &self.groups
+    }
+
+    /// Repeat the springs `times` times joined by `?`, and the groups as
+    /// many times.
+    pub fn unfold(&self, times: usize) -> Record {
+        let mut springs = Vec::new();
+        for i in 0..times {
+            if i > 0 {
+                springs.push(Spring::Unknown);
+            }
+            springs.extend_from_slice(&self.springs);
+        }
+        Record {
+            springs,
+            groups: self.groups.repeat(times),
+        }
+    }
+
+    /// Whether group `g` can start at spring `i`: it fits, covers no
+    /// operational spring, and is not directly followed by a damaged one.
+    fn fits(&self, i: usize, g: usize) -> bool {
+        let end = i + self.groups[g];
+        end <= self.springs.len()
+            && self.springs[i..end].iter().all(|s| s.may_be_damaged())
+            && self.springs.get(end).is_none_or(|s| s.may_be_operational())
+    }
+
+    /// `ways[i][g]` counts the arrangements of `springs[i..]` holding
+    /// exactly `groups[g..]`.
+    fn ways(&self) -> Vec<Vec<u64>> {
+        let (n, m) = (self.springs.len(), self.groups.len());
+        let mut ways = vec![vec![0_u64; m + 1]; n + 1];
+        ways[n][m] = 1;
+        for i in (0..n).rev() {
+            for g in 0..=m {
+                let mut count = 0;
+                if self.springs[i].may_be_operational() {
+                    count += ways[i + 1][g];
+                }
+                if g < m && self.fits(i, g) {
+                    // skip the group and the operational spring after it
+                    let next = (i + self.groups[g] + 1).min(n);
+                    count += ways[next][g + 1];
+                }
+                ways[i][g] = count;
+            }
+        }
+        ways
+    }
+
+    pub fn arrangements(&self) -> u64 {
+        self.ways()[0][0]
+    }
+
+    /// Every concrete arrangement, lazily, in the order where `.` sorts
+    /// before `#`.
+    pub fn iter_arrangements(&self) -> Arrangements<'_> {
+        let ways = self.ways();
+        Arrangements {
+            record: self,
+            total: ways[0][0],
+            ways,
+            next: 0,
+        }
+    }
+
+    /// The `k`th arrangement of [`Record::iter_arrangements`], found without
+    /// visiting the ones before it.
+    pub fn nth_arrangement(&self, k: u64) -> Option<Vec<Spring>> {
+        self.unrank(&self.ways(), k)
+    }
+
+    /// Walk down the `ways` table, going for `.` whenever `k` falls among
+    /// the arrangements that start with it.
+    fn unrank(&self, ways: &[Vec<u64>], mut k: u64) -> Option<Vec<Spring>> {
+        if k >= ways[0][0] {
+            return None;
+        }
+        let n = self.springs.len();
+        let mut springs = Vec::with_capacity(n);
+        let (mut i, mut g) = (0, 0);
+        while i < n {
+            if self.springs[i].may_be_operational() {
+                let with_dot = ways[i + 1][g];
+                if k < with_dot {
+                    springs.push(Spring::Operational);
+                    i += 1;
+                    continue;
+                }
+                k -= with_dot;
+            }
+            // whatever is left of k starts group g right here
+            let end = i + self.groups[g];
+            springs.resize(end, Spring::Damaged);
+            if end < n {
+                springs.push(Spring::Operational);
+            }
+            i = (end + 1).min(n);
+            g += 1;
+        }
+        Some(springs)
+    }
+
+    /// Whether a fully known row of springs agrees with this record.
+    pub fn matches(&self, candidate: &[Spring]) -> bool {
+        if candidate.len() != self.springs.len()
+            || candidate
+                .iter()
+                .zip(&self.springs)
+                .any(|(c, s)| *c == Spring::Unknown || (*s != Spring::Unknown && c != s))
+        {
+            return false;
+        }
+        let groups = render(candidate)
+            .split('.')
+            .filter(|g| !g.is_empty())
+            .map(str::len)
+            .collect::<Vec<_>>();
+        groups == self.groups
+    }
+
+    /// Count by trying every assignment of the unknown springs, as an
+    /// oracle for [`Record::arrangements`] on small records.
+    pub fn brute_force_arrangements(&self) -> u64 {
+        let unknowns = self
+            .springs
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| **s == Spring::Unknown)
+            .map(|(idx, _)| idx)
+            .collect::<Vec<_>>();
+        assert!(unknowns.len() < 32, "too many unknowns to brute force");
+        let mut candidate = self.springs.clone();
+        (0..1_u64 << unknowns.len())
+            .filter(|bits| {
+                for (bit, &idx) in unknowns.iter().enumerate() {
+                    candidate[idx] = match bits >> bit & 1 {
+                        1 => Spring::Damaged,
+                        _ => Spring::Operational,
+                    };
+                }
+                self.matches(&candidate)
+            })
+            .count() as u64
+    }
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let groups = self
+            .groups
+            .iter()
+            .map(|g| g.to_string())
+            .collect::<Vec<_>>();
+        write!(f, "{} {}", render(&self.springs), groups.join(","))
+    }
+}
+
+/// Iterator of [`Record::iter_arrangements`]. Each step unranks its index
+/// afresh, so nothing but the counting table is kept, and skipping ahead
+/// with `nth` costs as much as a single step.
+pub struct Arrangements<'a> {
+    record: &'a Record,
+    ways: Vec<Vec<u64>>,
+    next: u64,
+    total: u64,
+}
+
+impl Iterator for Arrangements<'_> {
+    type Item = Vec<Spring>;
+
+    fn next(&mut self) -> Option<Vec<Spring>> {
+        let springs = self.record.unrank(&self.ways, self.next)?;
+        self.next += 1;
+        Some(springs)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Vec<Spring>> {
+        self.next = self.next.saturating_add(n as u64).min(self.total);
+        self.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let left = self.total - self.next;
+        match usize::try_from(left) {
+            Ok(left) => (left, Some(left)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "???.### 1,1,3
+.??..??...?##. 1,1,3
+?#?#?#?#?#?#?#? 1,3,1,6
+????.#...#... 4,1,1
+????.######..#####. 1,6,5
+?###???????? 3,2,1";
+
+    #[test]
+    fn test_parse_record() {
+        let r = parse_record("#.? 1,1").unwrap();
+        assert_eq!(
+            r.springs(),
+            [Spring::Damaged, Spring::Operational, Spring::Unknown]
+        );
+        assert_eq!(r.groups(), [1, 1]);
+        let e = parse_record("#.x 1").unwrap_err();
+        assert_eq!(e.diagnostic().column, Some(3));
+        let e = parse_record("#.? 1,a").unwrap_err();
+        assert_eq!(e.diagnostic().column, Some(7));
+        assert!(parse_record("#.?").is_err());
+        let e = parse_records("# 1\n#. 0").unwrap_err();
+        assert_eq!(e.diagnostic().line, Some(2));
+    }
+
+    #[test]
+    fn test_arrangements() {
+        let counts = parse_records(EXAMPLE)
+            .unwrap()
+            .iter()
+            .map(Record::arrangements)
+            .collect::<Vec<_>>();
+        assert_eq!(counts, [1, 4, 1, 1, 4, 10]);
+    }
+
+    #[test]
+    fn test_unfold() {
+        let r = parse_record(".# 1").unwrap().unfold(3);
+        assert_eq!(r, parse_record(".#?.#?.# 1,1,1").unwrap());
+        let counts = parse_records(EXAMPLE)
+            .unwrap()
+            .iter()
+            .map(|r| r.unfold(5).arrangements())
+            .collect::<Vec<_>>();
+        assert_eq!(counts, [1, 16384, 1, 16, 2500, 506250]);
+    }
+
+    #[test]
+    fn test_iter_arrangements() {
+        let r = parse_record("?###???????? 3,2,1").unwrap();
+        let all = r
+            .iter_arrangements()
+            .map(|a| render(&a))
+            .collect::<Vec<_>>();
+        assert_eq!(all.len(), 10);
+        assert_eq!(all[0], ".###....##.#");
+        assert_eq!(all[9], ".###.##.#...");
+        let ordered = |a: &String| a.replace('.', "0").replace('#', "1");
+        assert!(all.windows(2).all(|w| ordered(&w[0]) < ordered(&w[1])));
+        for a in r.iter_arrangements() {
+            assert!(r.matches(&a), "{}", render(&a));
+        }
+        let display = r.to_string();
+        assert_eq!(display, "?###???????? 3,2,1");
+    }
+
+    #[test]
+    fn test_nth_arrangement() {
+        let r = parse_record("?###???????? 3,2,1").unwrap();
+        let all = r.iter_arrangements().collect::<Vec<_>>();
+        for (k, a) in all.iter().enumerate() {
+            assert_eq!(r.nth_arrangement(k as u64).as_ref(), Some(a));
+        }
+        assert_eq!(r.nth_arrangement(10), None);
+        assert_eq!(r.iter_arrangements().nth(7), Some(all[7].clone()));
+        assert_eq!(r.iter_arrangements().nth(70), None);
+
+        // far too many to list, but any one of them is cheap
+        let big = r.unfold(5);
+        let k = big.arrangements() - 1;
+        let last = big.nth_arrangement(k).unwrap();
+        assert!(big.matches(&last));
+        assert_eq!(big.iter_arrangements().size_hint(), (506250, Some(506250)));
+    }
+
+    #[test]
+    fn test_brute_force_agrees() {
+        for r in parse_records(EXAMPLE).unwrap() {
+            assert_eq!(r.brute_force_arrangements(), r.arrangements(), "{}", r);
+            let r2 = r.unfold(2);
+            if r2
+                .springs()
+                .iter()
+                .filter(|s| **s == Spring::Unknown)
+                .count()
+                <= 16
+            {
+                assert_eq!(r2.brute_force_arrangements(), r2.arrangements(), "{}", r2);
+            }
+        }
+    }
+}
@@ -1,3 +1,3 @@
 fn main() {
-    println!("Hello, world!");
+    aoc_core::run::<day12::Day12>();
 }
@@ -0,0 +1,4 @@
+aoc_core::examples! {
+    day12::Day12;
+    example: "example.txt", part1 = 21, part2 = 525152;
+}